@@ -1,14 +1,52 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum Role {
+    CheckpointOperator,
+    ProjectManager,
+    Pauser,
+    WhitelistManager,
+}
 
 #[multiversx_sc::module]
 pub trait AccessControlModule: crate::common_storage::CommonStorageModule {
-    fn require_caller_owner_or_signer(&self) {
+    #[only_owner]
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) {
+        let _ = self.role_members(role).insert(address);
+    }
+
+    #[only_owner]
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) {
+        let _ = self.role_members(role).swap_remove(&address);
+    }
+
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: ManagedAddress) -> bool {
+        self.role_members(role).contains(&address)
+    }
+
+    #[view(getRoleMembers)]
+    fn get_role_members(&self, role: Role) -> MultiValueEncoded<ManagedAddress> {
+        let mut members = MultiValueEncoded::new();
+        for member in self.role_members(role).iter() {
+            members.push(member);
+        }
+
+        members
+    }
+
+    fn require_caller_owner_or_role(&self, role: Role) {
         let caller = self.blockchain().get_caller();
         let owner = self.blockchain().get_owner_address();
-        let signer = self.signer().get();
         require!(
-            caller == owner || caller == signer,
-            "Only owner or signer may call this function"
+            caller == owner || self.role_members(role).contains(&caller),
+            "Permission denied"
         );
     }
+
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
 }
@@ -0,0 +1,195 @@
+multiversx_sc::imports!();
+
+use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
+
+use crate::{
+    claim::ClaimArgsWrapper,
+    claim_history::ClaimHistoryEntry,
+    common_storage::MAX_PERCENTAGE,
+    project::{ProjectId, MAX_PROJECT_EXPIRATION_WEEKS},
+    rewards::Week,
+};
+
+pub type BonusPoolAsMultiResult<M> =
+    MultiValue4<TokenIdentifier<M>, BigUint<M>, BigUint<M>, BigUint<M>>;
+
+#[multiversx_sc::module]
+pub trait BonusPoolModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::math::MathModule
+{
+    /// Adds the payment to the bonus pool of the given week. Each user claiming the week receives a share
+    /// of the pool, on top of the projects' rewards. Same as for projects, lkmex_rewards_percentage of the payment
+    /// is split by the users' share of the week's LKMEX staked amount, and the rest by their share of the
+    /// week's delegation supply.
+    ///
+    /// Pools may only be funded for future weeks, so no claims can have been made for the week yet.
+    #[only_owner]
+    #[payable("*")]
+    #[endpoint(fundBonusPool)]
+    fn fund_bonus_pool(&self, week: Week, lkmex_rewards_percentage: u64) {
+        let (payment_token, payment_amount) = self.call_value().single_fungible_esdt();
+        require!(week > self.get_current_week(), "Invalid week");
+        require!(
+            lkmex_rewards_percentage <= MAX_PERCENTAGE,
+            "Invalid percentage"
+        );
+
+        let lkmex_amount = &payment_amount * lkmex_rewards_percentage / MAX_PERCENTAGE;
+        self.bonus_pool(week, &payment_token)
+            .update(|pool| *pool += &payment_amount);
+        self.bonus_pool_lkmex(week, &payment_token)
+            .update(|pool| *pool += lkmex_amount);
+        self.bonus_pool_remaining(week, &payment_token)
+            .update(|remaining| *remaining += &payment_amount);
+        self.total_bonus_pools_remaining(&payment_token)
+            .update(|total| *total += &payment_amount);
+        let _ = self.bonus_pool_tokens(week).insert(payment_token.clone());
+        let _ = self.bonus_pool_token_ids().insert(payment_token);
+    }
+
+    /// Sends the undistributed bonus of the given week to the owner.
+    /// May only be called once the week can no longer be claimed.
+    #[only_owner]
+    #[endpoint(withdrawBonusPool)]
+    fn withdraw_bonus_pool(&self, week: Week) -> PaymentsVec<Self::Api> {
+        require!(
+            !self.is_bonus_pool_claimable(week, self.get_current_week()),
+            "Bonus pool still claimable"
+        );
+
+        let mut payments = PaymentsVec::new();
+        let mut token_ids = self.bonus_pool_tokens(week);
+        for token_id in token_ids.iter() {
+            self.bonus_pool(week, &token_id).clear();
+            self.bonus_pool_lkmex(week, &token_id).clear();
+            let remaining = self.bonus_pool_remaining(week, &token_id).take();
+            if remaining > 0 {
+                let total_mapper = self.total_bonus_pools_remaining(&token_id);
+                let new_total = total_mapper.get() - &remaining;
+                if new_total == 0 {
+                    total_mapper.clear();
+                    let _ = self.bonus_pool_token_ids().swap_remove(&token_id);
+                } else {
+                    total_mapper.set(&new_total);
+                }
+
+                payments.push(EsdtTokenPayment::new(token_id, 0, remaining));
+            }
+        }
+        token_ids.clear();
+
+        require!(!payments.is_empty(), "No bonus to withdraw");
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_multi(&caller, &payments);
+
+        payments
+    }
+
+    /// Adds the user's share of each bonus pool of the claimed week to the rewards.
+    /// Has to be called only once per user and week, i.e. when the week is first claimed.
+    ///
+    /// The history entries of the bonus have an empty project_id.
+    fn claim_bonus_for_week(
+        &self,
+        current_week: Week,
+        claim_arg: &ClaimArgsWrapper<Self::Api>,
+        rewards: &mut PaymentsVec<Self::Api>,
+        history_entries: &mut ManagedVec<ClaimHistoryEntry<Self::Api>>,
+    ) {
+        if !self.is_bonus_pool_claimable(claim_arg.week, current_week) {
+            return;
+        }
+
+        let timestamp = self.blockchain().get_block_timestamp();
+        for token_id in self.bonus_pool_tokens(claim_arg.week).iter() {
+            let pool = self.bonus_pool(claim_arg.week, &token_id).get();
+            let lkmex_pool = self.bonus_pool_lkmex(claim_arg.week, &token_id).get();
+            let delegation_pool = &pool - &lkmex_pool;
+            let bonus_delegation = self.calculate_ratio(
+                &delegation_pool,
+                &claim_arg.user_delegation_amount,
+                &claim_arg.checkpoint.total_delegation_supply,
+            );
+            let bonus_lkmex = self.calculate_ratio(
+                &lkmex_pool,
+                &claim_arg.user_lkmex_staked_amount,
+                &claim_arg.checkpoint.total_lkmex_staked,
+            );
+            let bonus = bonus_delegation + bonus_lkmex;
+            if bonus == 0 {
+                continue;
+            }
+
+            self.bonus_pool_remaining(claim_arg.week, &token_id)
+                .update(|remaining| *remaining -= &bonus);
+            self.total_bonus_pools_remaining(&token_id)
+                .update(|total| *total -= &bonus);
+            history_entries.push(ClaimHistoryEntry {
+                week: claim_arg.week,
+                project_id: ProjectId::new(),
+                token_id: token_id.clone(),
+                amount: bonus.clone(),
+                timestamp,
+            });
+            rewards.push(EsdtTokenPayment::new(token_id, 0, bonus));
+        }
+    }
+
+    #[inline]
+    fn is_bonus_pool_claimable(&self, week: Week, current_week: Week) -> bool {
+        current_week <= week + MAX_PROJECT_EXPIRATION_WEEKS
+    }
+
+    /// Returns the bonus pools of the given week. The results are, in order:
+    /// - token_id
+    /// - total_amount - the amount the users' shares are calculated from
+    /// - lkmex_amount - the part of total_amount split by the LKMEX staked amount
+    /// - remaining_amount - the amount not yet claimed
+    #[view(getBonusPoolsForWeek)]
+    fn get_bonus_pools_for_week(
+        &self,
+        week: Week,
+    ) -> MultiValueEncoded<BonusPoolAsMultiResult<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.bonus_pool_tokens(week).iter() {
+            let total_amount = self.bonus_pool(week, &token_id).get();
+            let lkmex_amount = self.bonus_pool_lkmex(week, &token_id).get();
+            let remaining_amount = self.bonus_pool_remaining(week, &token_id).get();
+            result.push((token_id, total_amount, lkmex_amount, remaining_amount).into());
+        }
+
+        result
+    }
+
+    #[storage_mapper("bonusPool")]
+    fn bonus_pool(&self, week: Week, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("bonusPoolLkmex")]
+    fn bonus_pool_lkmex(
+        &self,
+        week: Week,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("bonusPoolRemaining")]
+    fn bonus_pool_remaining(
+        &self,
+        week: Week,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("bonusPoolTokens")]
+    fn bonus_pool_tokens(&self, week: Week) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Sum of the undistributed bonus of all the weeks, used for the solvency report
+    #[storage_mapper("totalBonusPoolsRemaining")]
+    fn total_bonus_pools_remaining(&self, token_id: &TokenIdentifier)
+        -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("bonusPoolTokenIds")]
+    fn bonus_pool_token_ids(&self) -> UnorderedSetMapper<TokenIdentifier>;
+}
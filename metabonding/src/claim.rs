@@ -4,7 +4,10 @@ multiversx_sc::derive_imports!();
 use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
 
 use crate::{
+    claim_history::ClaimHistoryEntry,
     claim_progress::{ClaimFlag, ClaimProgressTracker, ShiftingClaimProgress},
+    common_storage::MAX_PERCENTAGE,
+    lkmex_weighting::LkmexWeighting,
     project::{ProjIdsVec, Project, ProjectId},
     rewards::{RewardsCheckpoint, Week},
     validation::Signature,
@@ -14,8 +17,11 @@ pub static NO_CLAIM_ARGS_ERR_MSG: &[u8] = b"No claim args";
 
 const MAX_CLAIM_ARG_PAIRS: usize = 5;
 const CLAIM_NR_ARGS_PER_PAIR: usize = 4;
+const ENERGY_CLAIM_NR_ARGS_PER_PAIR: usize = 5;
 
 pub type ClaimArgPair<M> = MultiValue4<Week, BigUint<M>, BigUint<M>, Signature<M>>;
+pub type EnergyClaimArgPair<M> =
+    MultiValue5<Week, BigUint<M>, BigUint<M>, BigUint<M>, Signature<M>>;
 pub type ClaimArgArray<M> = ArrayVec<ClaimArgsWrapper<M>, MAX_CLAIM_ARG_PAIRS>;
 pub type FlagsArray<M> = ArrayVec<ClaimFlag<M>, MAX_CLAIM_ARG_PAIRS>;
 
@@ -29,13 +35,14 @@ pub struct ClaimArgsWrapper<M: ManagedTypeApi> {
     pub week: Week,
     pub user_delegation_amount: BigUint<M>,
     pub user_lkmex_staked_amount: BigUint<M>,
+    pub opt_user_energy: Option<BigUint<M>>,
     pub checkpoint: RewardsCheckpoint<M>,
     pub signature: Signature<M>,
 }
 
 #[multiversx_sc::module]
 pub trait ClaimModule:
-    multiversx_sc_modules::pause::PauseModule
+    crate::pause::PauseModule
     + crate::project::ProjectModule
     + crate::access_control::AccessControlModule
     + crate::common_storage::CommonStorageModule
@@ -43,13 +50,26 @@ pub trait ClaimModule:
     + crate::validation::ValidationModule
     + crate::rewards::RewardsModule
     + crate::claim_progress::ClaimProgressModule
-    + sc_whitelist_module::SCWhitelistModule
+    + crate::vesting::VestingModule
+    + crate::claim_delegates::ClaimDelegatesModule
+    + crate::proxy_settings::ProxySettingsModule
+    + crate::project_opt_out::ProjectOptOutModule
+    + crate::lkmex_weighting::LkmexWeightingModule
+    + crate::rollover::RolloverModule
+    + crate::claim_history::ClaimHistoryModule
+    + crate::protocol_fee::ProtocolFeeModule
+    + crate::bonus_pool::BonusPoolModule
+    + crate::events::EventsModule
+    + crate::sc_whitelist::SCWhitelistModule
 {
     /// Claims rewards for the given user.
-    /// May only be different from caller for whitelisted proxy contracts.
+    /// May only be different from caller for whitelisted proxy contracts, or for the user's claim delegates.
     /// If the user performs their own claim, this address should be their own.
+    /// Rewards claimed by a delegate are sent to the user.
     ///
     /// Claims rewards for the given weeks. Maximum of MAX_CLAIM_ARG_PAIRS weeks can be claimed per call.
+    /// Rewards of projects with a vesting schedule are not sent, but added to the user's vesting position instead.
+    /// Projects in the user's opt-out list are skipped.
     /// Arguments are pairs of:
     /// week: number,
     /// user_delegation_amount: BigUint,
@@ -61,12 +81,55 @@ pub trait ClaimModule:
         original_caller: ManagedAddress,
         raw_claim_args: MultiValueEncoded<ClaimArgPair<Self::Api>>,
     ) -> PaymentsVec<Self::Api> {
-        let all_projects = self.get_all_project_ids();
         self.claim_common(
             original_caller,
-            &all_projects,
-            &all_projects,
-            raw_claim_args,
+            None,
+            None,
+            self.collect_claim_args(raw_claim_args),
+        )
+    }
+
+    /// Same as claimRewards, but rewards are sent to the given recipient instead.
+    /// Claim delegates may only give the user as recipient.
+    /// The recipient is part of the signed data, so the signatures for this endpoint are given by
+    /// `sign_ed25519(week_number + user_address + user_egld_staked_amount + user_lkmex_staked_amount + recipient)`
+    #[endpoint(claimRewardsTo)]
+    fn claim_rewards_to(
+        &self,
+        original_caller: ManagedAddress,
+        recipient: ManagedAddress,
+        raw_claim_args: MultiValueEncoded<ClaimArgPair<Self::Api>>,
+    ) -> PaymentsVec<Self::Api> {
+        self.claim_common(
+            original_caller,
+            Some(recipient),
+            None,
+            self.collect_claim_args(raw_claim_args),
+        )
+    }
+
+    /// Same as claimRewards, but each week's arguments also include the user's energy,
+    /// used for projects whose LKMEX rewards are split by energy.
+    /// Arguments are pairs of:
+    /// week: number,
+    /// user_delegation_amount: BigUint,
+    /// user_lkmex_staked_amount: BigUint,
+    /// user_energy: BigUint,
+    /// signature: 120 bytes
+    ///
+    /// The signatures are given by
+    /// `sign_ed25519(week_number + user_address + user_egld_staked_amount + user_lkmex_staked_amount + user_energy)`
+    #[endpoint(claimRewardsWithEnergy)]
+    fn claim_rewards_with_energy(
+        &self,
+        original_caller: ManagedAddress,
+        raw_claim_args: MultiValueEncoded<EnergyClaimArgPair<Self::Api>>,
+    ) -> PaymentsVec<Self::Api> {
+        self.claim_common(
+            original_caller,
+            None,
+            None,
+            self.collect_energy_claim_args(raw_claim_args),
         )
     }
 
@@ -77,58 +140,99 @@ pub trait ClaimModule:
         projects_to_claim: ProjIdsVec<Self::Api>,
         raw_claim_args: MultiValueEncoded<ClaimArgPair<Self::Api>>,
     ) -> PaymentsVec<Self::Api> {
-        let all_projects = self.get_all_project_ids();
         self.claim_common(
             original_caller,
-            &projects_to_claim,
-            &all_projects,
-            raw_claim_args,
+            None,
+            Some(projects_to_claim),
+            self.collect_claim_args(raw_claim_args),
         )
     }
 
-    fn claim_common(
+    /// Same as claimPartialRewards, but rewards are sent to the given recipient instead.
+    /// Signatures must include the recipient, same as for claimRewardsTo.
+    #[endpoint(claimPartialRewardsTo)]
+    fn claim_partial_rewards_to(
         &self,
         original_caller: ManagedAddress,
-        projects_to_claim: &ProjIdsVec<Self::Api>,
-        all_projects: &ProjIdsVec<Self::Api>,
+        recipient: ManagedAddress,
+        projects_to_claim: ProjIdsVec<Self::Api>,
         raw_claim_args: MultiValueEncoded<ClaimArgPair<Self::Api>>,
+    ) -> PaymentsVec<Self::Api> {
+        self.claim_common(
+            original_caller,
+            Some(recipient),
+            Some(projects_to_claim),
+            self.collect_claim_args(raw_claim_args),
+        )
+    }
+
+    fn claim_common(
+        &self,
+        original_caller: ManagedAddress,
+        opt_recipient: Option<ManagedAddress>,
+        opt_projects_to_claim: Option<ProjIdsVec<Self::Api>>,
+        mut args: ClaimArgArray<Self::Api>,
     ) -> PaymentsVec<Self::Api> {
         require!(self.not_paused(), "May not claim rewards while paused");
 
         let caller = self.blockchain().get_caller();
-        if caller != original_caller {
+        let is_proxy_claim = !self.is_user_or_delegate(&caller, &original_caller);
+        if is_proxy_claim {
             self.require_sc_address_whitelisted(&caller);
+            self.require_proxy_enabled(&caller);
+        } else if caller != original_caller {
+            // delegates may only claim the rewards for the user
+            if let Some(recipient) = &opt_recipient {
+                require!(recipient == &original_caller, "Invalid rewards recipient");
+            }
         }
 
+        let rewards_recipient = match &opt_recipient {
+            Some(recipient) => recipient.clone(),
+            None if is_proxy_claim => caller.clone(),
+            None => original_caller.clone(),
+        };
+
         let current_week = self.get_current_week();
         let mut claim_progress = self.get_claim_progress(&original_caller, current_week);
 
         let last_checkpoint_week = self.get_last_checkpoint_week();
-        let mut args = self.collect_claim_args(raw_claim_args);
         self.sort_claim_args(&mut args);
         self.validate_claim_args(
             &original_caller,
+            opt_recipient.as_ref(),
             &args,
             &claim_progress,
             last_checkpoint_week,
         );
 
+        let mut history_entries = ManagedVec::new();
         let rewards = self.claim_all_project_rewards(
+            &original_caller,
             current_week,
             &args,
             &mut claim_progress,
-            projects_to_claim,
-            all_projects,
+            opt_projects_to_claim.as_ref(),
+            &mut history_entries,
         );
         self.claim_progress(&original_caller).set(claim_progress);
+        self.record_claim_history(&original_caller, &history_entries);
+
+        if is_proxy_claim {
+            self.update_proxy_claimed_amounts(&caller, current_week, &rewards);
+        }
 
         if !rewards.is_empty() {
-            self.send().direct_multi(&caller, &rewards);
+            self.send().direct_multi(&rewards_recipient, &rewards);
         }
 
         rewards
     }
 
+    fn is_user_or_delegate(&self, caller: &ManagedAddress, user: &ManagedAddress) -> bool {
+        caller == user || self.is_claim_delegate(user, caller)
+    }
+
     fn sort_claim_args(&self, claim_args: &mut ClaimArgArray<Self::Api>) {
         claim_args.sort_unstable_by(|a, b| a.week.cmp(&b.week));
     }
@@ -147,17 +251,43 @@ pub trait ClaimModule:
         for raw_arg in raw_claim_args {
             let (week, user_delegation_amount, user_lkmex_staked_amount, signature) =
                 raw_arg.into_tuple();
-            let checkpoint = self
-                .rewards_checkpoints()
-                .get_or_else(week, RewardsCheckpoint::default);
+            let arg = self.new_claim_arg(
+                week,
+                user_delegation_amount,
+                user_lkmex_staked_amount,
+                None,
+                signature,
+            );
+
+            unsafe {
+                array.push_unchecked(arg);
+            }
+        }
+
+        array
+    }
+
+    fn collect_energy_claim_args(
+        &self,
+        raw_claim_args: MultiValueEncoded<EnergyClaimArgPair<Self::Api>>,
+    ) -> ClaimArgArray<Self::Api> {
+        require!(!raw_claim_args.is_empty(), NO_CLAIM_ARGS_ERR_MSG);
+        require!(
+            raw_claim_args.raw_len() / ENERGY_CLAIM_NR_ARGS_PER_PAIR <= MAX_CLAIM_ARG_PAIRS,
+            "Too many arguments"
+        );
 
-            let arg = ClaimArgsWrapper {
+        let mut array = ArrayVec::new();
+        for raw_arg in raw_claim_args {
+            let (week, user_delegation_amount, user_lkmex_staked_amount, user_energy, signature) =
+                raw_arg.into_tuple();
+            let arg = self.new_claim_arg(
                 week,
                 user_delegation_amount,
                 user_lkmex_staked_amount,
-                checkpoint,
+                Some(user_energy),
                 signature,
-            };
+            );
 
             unsafe {
                 array.push_unchecked(arg);
@@ -167,32 +297,87 @@ pub trait ClaimModule:
         array
     }
 
+    fn new_claim_arg(
+        &self,
+        week: Week,
+        user_delegation_amount: BigUint,
+        user_lkmex_staked_amount: BigUint,
+        opt_user_energy: Option<BigUint>,
+        signature: Signature<Self::Api>,
+    ) -> ClaimArgsWrapper<Self::Api> {
+        let checkpoint = self
+            .rewards_checkpoints()
+            .get_or_else(week, RewardsCheckpoint::default);
+
+        ClaimArgsWrapper {
+            week,
+            user_delegation_amount,
+            user_lkmex_staked_amount,
+            opt_user_energy,
+            checkpoint,
+            signature,
+        }
+    }
+
+    /// Only the projects active in the claimed weeks are loaded.
+    /// If no projects are given, all of them are claimed.
     fn claim_all_project_rewards(
         &self,
+        user: &ManagedAddress,
         current_week: Week,
         claim_args: &ClaimArgArray<Self::Api>,
         claim_progress: &mut ShiftingClaimProgress<Self::Api>,
-        projects_to_claim: &ProjIdsVec<Self::Api>,
-        all_projects: &ProjIdsVec<Self::Api>,
+        opt_projects_to_claim: Option<&ProjIdsVec<Self::Api>>,
+        history_entries: &mut ManagedVec<ClaimHistoryEntry<Self::Api>>,
     ) -> PaymentsVec<Self::Api> {
+        let mut all_projects = ProjIdsVec::new();
+        for id in self.projects().keys() {
+            all_projects.push(id);
+        }
+
+        // the bonus is given once per week, when the week is first claimed
+        let mut all_rewards = PaymentsVec::new();
         for arg in claim_args {
             let flags_for_week = claim_progress.get_claim_flags_for_week(arg.week);
             if matches!(flags_for_week, ClaimFlag::NotClaimed) {
-                claim_progress.set_claimed_for_week(arg.week, all_projects.clone())
+                claim_progress.set_claimed_for_week(arg.week, all_projects.clone());
+                self.claim_bonus_for_week(current_week, arg, &mut all_rewards, history_entries);
             }
         }
 
-        let mut all_rewards = PaymentsVec::new();
+        let mut active_projects = ProjIdsVec::new();
+        for arg in claim_args {
+            for id in self.projects_active_in_week(arg.week).iter() {
+                if active_projects.find(&id).is_none() {
+                    active_projects.push(id);
+                }
+            }
+        }
+
+        let projects_to_claim = opt_projects_to_claim.unwrap_or(&all_projects);
         let projects_mapper = self.projects();
         for id in projects_to_claim {
+            // projects not active in any of the claimed weeks can't give rewards, so they're not loaded
+            if active_projects.find(&id).is_none() {
+                self.mark_project_claimed(&id, claim_args, claim_progress);
+                continue;
+            }
+
             let opt_project = projects_mapper.get(&id);
             if opt_project.is_none() {
                 continue;
             }
 
             let project = unsafe { opt_project.unwrap_unchecked() };
-            let opt_rewards =
-                self.claim_for_project(current_week, &id, project, claim_args, claim_progress);
+            let opt_rewards = self.claim_for_project(
+                user,
+                current_week,
+                &id,
+                project,
+                claim_args,
+                claim_progress,
+                history_entries,
+            );
             if let Some(rewards) = opt_rewards {
                 all_rewards.push(rewards);
             }
@@ -201,15 +386,41 @@ pub trait ClaimModule:
         all_rewards
     }
 
+    fn mark_project_claimed(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        claim_args: &ClaimArgArray<Self::Api>,
+        claim_progress: &mut ShiftingClaimProgress<Self::Api>,
+    ) {
+        for arg in claim_args {
+            let flags_mut = claim_progress.get_mut_claim_flags_for_week(arg.week);
+            let unclaimed_proj_ref = flags_mut.get_mut_unclaimed_proj();
+            if let Some(proj_index) = unclaimed_proj_ref.find(project_id) {
+                unclaimed_proj_ref.remove(proj_index);
+            }
+        }
+    }
+
     fn claim_for_project(
         &self,
+        user: &ManagedAddress,
         current_week: Week,
         project_id: &ProjectId<Self::Api>,
         project: Project<Self::Api>,
         claim_args: &ClaimArgArray<Self::Api>,
         claim_progress: &mut ShiftingClaimProgress<Self::Api>,
+        history_entries: &mut ManagedVec<ClaimHistoryEntry<Self::Api>>,
     ) -> Option<EsdtTokenPayment> {
+        let timestamp = self.blockchain().get_block_timestamp();
+        let opted_out = self.is_opted_out(user, project_id);
+        let rollover_enabled = self.rollover_projects().contains(project_id);
+        let fee_percentage = self.get_protocol_fee_percentage(project_id);
+        let reward_nonce = self.project_reward_nonce(project_id).get();
+        let energy_weighted = project.lkmex_reward_supply > 0
+            && self.project_lkmex_weighting(project_id).get() == LkmexWeighting::Energy
+            && self.rewards_deposited(project_id).get();
         let mut rewards_for_project = BigUint::zero();
+        let mut fee_for_project = BigUint::zero();
         for arg in claim_args {
             let flags_mut = claim_progress.get_mut_claim_flags_for_week(arg.week);
             let unclaimed_proj_ref = flags_mut.get_mut_unclaimed_proj();
@@ -218,16 +429,51 @@ pub trait ClaimModule:
                 continue;
             }
 
-            let opt_weekly_reward =
-                self.get_weekly_reward_for_project(project_id, &project, current_week, arg);
-            if let Some(weekly_reward) = opt_weekly_reward {
-                rewards_for_project += weekly_reward;
+            // opted out projects are marked as claimed, without giving any rewards.
+            // With rollover, their rewards are still counted as claimed, so they're not redistributed.
+            if !opted_out || rollover_enabled {
+                // without the user's energy, the project is left unclaimed for the week,
+                // to be claimed later through claimRewardsWithEnergy
+                if energy_weighted
+                    && self.is_in_range(arg.week, project.start_week, project.end_week)
+                    && !self.has_energy_for_week(arg)
+                {
+                    continue;
+                }
+
+                let opt_weekly_reward =
+                    self.get_weekly_reward_for_project(project_id, &project, current_week, arg);
+                if let Some(weekly_reward) = opt_weekly_reward {
+                    if rollover_enabled {
+                        self.add_weekly_claimed_amount(project_id, arg.week, &weekly_reward);
+                    }
+
+                    if !opted_out {
+                        let weekly_fee = &weekly_reward * fee_percentage / MAX_PERCENTAGE;
+                        let user_weekly_reward = weekly_reward - &weekly_fee;
+                        fee_for_project += weekly_fee;
+                        rewards_for_project += &user_weekly_reward;
+                        history_entries.push(ClaimHistoryEntry {
+                            week: arg.week,
+                            project_id: project_id.clone(),
+                            token_id: project.reward_token.clone(),
+                            amount: user_weekly_reward,
+                            timestamp,
+                        });
+                    }
+                }
             }
 
             let proj_index = unsafe { opt_index.unwrap_unchecked() };
             unclaimed_proj_ref.remove(proj_index);
         }
 
+        if fee_for_project > 0 {
+            self.leftover_project_funds(project_id)
+                .update(|leftover| *leftover -= &fee_for_project);
+            self.add_to_treasury(&project.reward_token, reward_nonce, &fee_for_project);
+        }
+
         if rewards_for_project == 0 {
             return None;
         }
@@ -235,7 +481,22 @@ pub trait ClaimModule:
         self.leftover_project_funds(project_id)
             .update(|leftover| *leftover -= &rewards_for_project);
 
-        let reward_payment = EsdtTokenPayment::new(project.reward_token, 0, rewards_for_project);
+        let vesting_mapper = self.project_vesting(project_id);
+        if !vesting_mapper.is_empty() {
+            let schedule = vesting_mapper.get();
+            self.add_to_vesting_position(
+                user,
+                project_id,
+                &project,
+                &schedule,
+                &rewards_for_project,
+            );
+
+            return None;
+        }
+
+        let reward_payment =
+            EsdtTokenPayment::new(project.reward_token, reward_nonce, rewards_for_project);
         Some(reward_payment)
     }
 
@@ -257,14 +518,36 @@ pub trait ClaimModule:
             );
         let last_checkpoint_week = self.get_last_checkpoint_week();
 
+        // the claim progress covers the longest expiration period,
+        // so weeks are only listed while some of their rewards are still claimable
         let mut claimable_weeks = MultiValueEncoded::new();
         for week in start_week..=last_checkpoint_week {
             let claim_flags = claim_progress.get_claim_flags_for_week(week);
             match claim_flags {
-                ClaimFlag::NotClaimed => claimable_weeks.push((week, ClaimableTokens::All).into()),
+                ClaimFlag::NotClaimed => {
+                    let has_claimable_projects =
+                        self.projects_active_in_week(week).iter().any(|project_id| {
+                            self.is_project_id_claimable(&project_id, week, current_week)
+                        });
+                    let has_claimable_bonus = !self.bonus_pool_tokens(week).is_empty()
+                        && self.is_bonus_pool_claimable(week, current_week);
+                    if has_claimable_projects || has_claimable_bonus {
+                        claimable_weeks.push((week, ClaimableTokens::All).into());
+                    }
+                }
                 ClaimFlag::Claimed { unclaimed_projects } => {
+                    let mut still_claimable = ProjIdsVec::new();
+                    for project_id in unclaimed_projects.iter() {
+                        if self.is_project_id_claimable(&project_id, week, current_week) {
+                            still_claimable.push((*project_id).clone());
+                        }
+                    }
+                    if still_claimable.is_empty() {
+                        continue;
+                    }
+
                     let partial = ClaimableTokens::Partial {
-                        unclaimed_projects: unclaimed_projects.clone(),
+                        unclaimed_projects: still_claimable,
                     };
                     claimable_weeks.push((week, partial).into());
                 }
@@ -273,4 +556,18 @@ pub trait ClaimModule:
 
         claimable_weeks
     }
+
+    fn is_project_id_claimable(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        week: Week,
+        current_week: Week,
+    ) -> bool {
+        match self.projects().get(project_id) {
+            Some(project) => {
+                self.is_project_claimable_for_week(project_id, &project, week, current_week)
+            }
+            None => false,
+        }
+    }
 }
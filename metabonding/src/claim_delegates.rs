@@ -0,0 +1,48 @@
+multiversx_sc::imports!();
+
+pub const MAX_CLAIM_DELEGATES: usize = 10;
+
+#[multiversx_sc::module]
+pub trait ClaimDelegatesModule {
+    /// Allows the given address to claim rewards on behalf of the caller.
+    /// Rewards claimed by a delegate are always sent to the user, never to the delegate.
+    #[endpoint(addClaimDelegate)]
+    fn add_claim_delegate(&self, delegate: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(caller != delegate, "Cannot add self as delegate");
+
+        let mut delegates_mapper = self.claim_delegates(&caller);
+        require!(
+            delegates_mapper.len() < MAX_CLAIM_DELEGATES,
+            "Too many delegates"
+        );
+
+        let added = delegates_mapper.insert(delegate);
+        require!(added, "Address is already a delegate");
+    }
+
+    #[endpoint(removeClaimDelegate)]
+    fn remove_claim_delegate(&self, delegate: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let removed = self.claim_delegates(&caller).swap_remove(&delegate);
+        require!(removed, "Address is not a delegate");
+    }
+
+    #[view(getClaimDelegates)]
+    fn get_claim_delegates(&self, user: ManagedAddress) -> MultiValueEncoded<ManagedAddress> {
+        let mut delegates = MultiValueEncoded::new();
+        for delegate in self.claim_delegates(&user).iter() {
+            delegates.push(delegate);
+        }
+
+        delegates
+    }
+
+    #[inline]
+    fn is_claim_delegate(&self, user: &ManagedAddress, address: &ManagedAddress) -> bool {
+        self.claim_delegates(user).contains(address)
+    }
+
+    #[storage_mapper("claimDelegates")]
+    fn claim_delegates(&self, user: &ManagedAddress) -> UnorderedSetMapper<ManagedAddress>;
+}
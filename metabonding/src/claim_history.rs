@@ -0,0 +1,85 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{project::ProjectId, rewards::Week};
+
+pub const MAX_CLAIM_HISTORY_ENTRIES: usize = 100;
+
+/// Bonus pool rewards are recorded with an empty project_id
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone)]
+pub struct ClaimHistoryEntry<M: ManagedTypeApi> {
+    pub week: Week,
+    pub project_id: ProjectId<M>,
+    pub token_id: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait ClaimHistoryModule: crate::events::EventsModule {
+    /// Returns the user's claim history, newest entries first.
+    /// Only the last MAX_CLAIM_HISTORY_ENTRIES entries are kept.
+    /// Arguments:
+    /// - from - the number of newest entries to skip
+    /// - size - the maximum number of entries to return
+    #[view(getClaimHistory)]
+    fn get_claim_history(
+        &self,
+        user: ManagedAddress,
+        from: usize,
+        size: usize,
+    ) -> MultiValueEncoded<ClaimHistoryEntry<Self::Api>> {
+        require!(size <= MAX_CLAIM_HISTORY_ENTRIES, "Invalid page size");
+
+        let total_entries = self.claim_history_len(&user).get();
+        let stored_entries = core::cmp::min(total_entries, MAX_CLAIM_HISTORY_ENTRIES);
+        let end = core::cmp::min(from.saturating_add(size), stored_entries);
+
+        let mut result = MultiValueEncoded::new();
+        for i in from..end {
+            let slot = (total_entries - 1 - i) % MAX_CLAIM_HISTORY_ENTRIES;
+            result.push(self.claim_history_entry(&user, slot).get());
+        }
+
+        result
+    }
+
+    #[view(getClaimHistoryLength)]
+    fn get_claim_history_length(&self, user: ManagedAddress) -> usize {
+        let total_entries = self.claim_history_len(&user).get();
+
+        core::cmp::min(total_entries, MAX_CLAIM_HISTORY_ENTRIES)
+    }
+
+    /// Entries are kept in a ring buffer, overwriting the oldest entry once full
+    fn record_claim_history(
+        &self,
+        user: &ManagedAddress,
+        entries: &ManagedVec<ClaimHistoryEntry<Self::Api>>,
+    ) {
+        if entries.is_empty() {
+            return;
+        }
+
+        let len_mapper = self.claim_history_len(user);
+        let mut total_entries = len_mapper.get();
+        for entry in entries {
+            let slot = total_entries % MAX_CLAIM_HISTORY_ENTRIES;
+            self.claim_history_entry(user, slot).set(&entry);
+            total_entries += 1;
+        }
+        len_mapper.set(total_entries);
+
+        self.emit_claim_rewards_event(user, entries);
+    }
+
+    #[storage_mapper("claimHistoryLen")]
+    fn claim_history_len(&self, user: &ManagedAddress) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("claimHistoryEntry")]
+    fn claim_history_entry(
+        &self,
+        user: &ManagedAddress,
+        slot: usize,
+    ) -> SingleValueMapper<ClaimHistoryEntry<Self::Api>>;
+}
@@ -2,7 +2,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::{
-    project::{ProjIdsVec, PROJECT_EXPIRATION_WEEKS},
+    project::{ProjIdsVec, MAX_PROJECT_EXPIRATION_WEEKS},
     rewards::{Week, FIRST_WEEK},
     validation::INVALID_WEEK_NR_ERR_MSG,
 };
@@ -32,7 +32,7 @@ impl<M: ManagedTypeApi> ClaimFlag<M> {
     }
 }
 
-const CLAIM_FLAGS_LEN: usize = PROJECT_EXPIRATION_WEEKS + 1;
+pub const CLAIM_FLAGS_LEN: usize = MAX_PROJECT_EXPIRATION_WEEKS + 1;
 type ClaimFlagsArray<M> = ArrayVec<ClaimFlag<M>, CLAIM_FLAGS_LEN>;
 
 fn default_claim_flags<M: ManagedTypeApi>() -> ClaimFlagsArray<M> {
@@ -81,6 +81,36 @@ impl<M: ManagedTypeApi> ShiftingClaimProgress<M> {
         week - self.first_index_week
     }
 
+    /// Progress saved before the claim window was extended has fewer flags.
+    /// The missing weeks before the saved window could no longer be claimed, so they're marked as claimed.
+    fn extend_window_if_needed(&mut self) {
+        let saved_len = self.claim_flags.len();
+        if saved_len == CLAIM_FLAGS_LEN {
+            return;
+        }
+
+        let nr_missing_weeks = CLAIM_FLAGS_LEN - saved_len;
+        let nr_prepended_weeks =
+            core::cmp::min(nr_missing_weeks, self.first_index_week - FIRST_WEEK);
+        let mut claim_flags = ClaimFlagsArray::new();
+        for _ in 0..nr_prepended_weeks {
+            unsafe {
+                claim_flags.push_unchecked(ClaimFlag::Claimed {
+                    unclaimed_projects: ManagedVec::new(),
+                })
+            };
+        }
+        for flag in self.claim_flags.drain(..) {
+            unsafe { claim_flags.push_unchecked(flag) };
+        }
+        for _ in (nr_prepended_weeks + saved_len)..CLAIM_FLAGS_LEN {
+            unsafe { claim_flags.push_unchecked(ClaimFlag::NotClaimed) };
+        }
+
+        self.claim_flags = claim_flags;
+        self.first_index_week -= nr_prepended_weeks;
+    }
+
     fn shift_if_needed(&mut self, current_week: Week) {
         if current_week <= CLAIM_FLAGS_LEN {
             return;
@@ -150,6 +180,7 @@ pub trait ClaimProgressModule {
         let mapper = self.claim_progress(user);
         if !mapper.is_empty() {
             let mut existing_progress = mapper.get();
+            existing_progress.extend_window_if_needed();
             existing_progress.shift_if_needed(current_week);
 
             return existing_progress;
@@ -203,6 +234,10 @@ mod claim_progress_tests {
                 claimed.clone(),
                 claimed.clone(),
                 not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
             ]
             .into(),
             first_index_week: FIRST_WEEK,
@@ -219,6 +254,10 @@ mod claim_progress_tests {
                         claimed.clone(),
                         claimed.clone(),
                         not_claimed.clone(),
+                        not_claimed.clone(),
+                        not_claimed.clone(),
+                        not_claimed.clone(),
+                        not_claimed.clone(),
                     ]
                     && progress.first_index_week == FIRST_WEEK
             );
@@ -236,6 +275,10 @@ mod claim_progress_tests {
                     claimed.clone(),
                     not_claimed.clone(),
                     not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
                 ]
                 && progress.first_index_week == expected_first_index_week
         );
@@ -252,6 +295,10 @@ mod claim_progress_tests {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
                 ]
                 && progress.first_index_week == expected_first_index_week
         );
@@ -263,6 +310,10 @@ mod claim_progress_tests {
             claimed.clone(),
             claimed.clone(),
             claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
         ]
         .into();
 
@@ -281,6 +332,10 @@ mod claim_progress_tests {
             claimed.clone(),
             claimed.clone(),
             claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
+            claimed.clone(),
         ]
         .into();
 
@@ -290,13 +345,82 @@ mod claim_progress_tests {
         assert!(
             progress.claim_flags.as_slice()
                 == [
-                    claimed,
+                    claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
+                    not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed.clone(),
-                    not_claimed,
                 ]
                 && progress.first_index_week == expected_first_index_week
         );
     }
+
+    #[test]
+    fn claim_progress_extend_window_test() {
+        let _ = DebugApi::dummy();
+        let not_claimed = ClaimFlag::NotClaimed;
+        let claimed = ClaimFlag::Claimed {
+            unclaimed_projects: ManagedVec::new(),
+        };
+
+        // saved with a 5 weeks window, at week 10
+        let mut saved_flags = ClaimFlagsArray::new();
+        for flag in [
+            claimed.clone(),
+            not_claimed.clone(),
+            claimed.clone(),
+            not_claimed.clone(),
+            not_claimed.clone(),
+        ] {
+            saved_flags.push(flag);
+        }
+        let mut progress = ShiftingClaimProgress::<DebugApi> {
+            claim_flags: saved_flags.clone(),
+            first_index_week: 6,
+        };
+
+        // weeks before the saved window are marked as claimed
+        progress.extend_window_if_needed();
+        assert_eq!(
+            progress.claim_flags.as_slice(),
+            [
+                claimed.clone(),
+                claimed.clone(),
+                claimed.clone(),
+                claimed.clone(),
+                claimed.clone(),
+                not_claimed.clone(),
+                claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+            ]
+        );
+        assert_eq!(progress.first_index_week, 2);
+
+        // saved before the window was full, the new weeks are added at the end
+        let mut progress = ShiftingClaimProgress::<DebugApi> {
+            claim_flags: saved_flags,
+            first_index_week: FIRST_WEEK,
+        };
+        progress.extend_window_if_needed();
+        assert_eq!(
+            progress.claim_flags.as_slice(),
+            [
+                claimed.clone(),
+                not_claimed.clone(),
+                claimed,
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed.clone(),
+                not_claimed,
+            ]
+        );
+        assert_eq!(progress.first_index_week, FIRST_WEEK);
+    }
 }
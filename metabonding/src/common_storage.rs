@@ -1,15 +1,41 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
-use crate::project::Epoch;
+use crate::project::{Epoch, Timestamp};
 
 pub const EPOCHS_IN_WEEK: Epoch = 7;
 pub const MAX_PERCENTAGE: u64 = 100;
+pub const MONDAY_19_02_2024_GMT_TIMESTAMP: Timestamp = 1_708_300_800;
+
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Clone, Copy)]
+pub enum TimekeepingMode {
+    Epochs,
+    Timestamps,
+}
 
 #[multiversx_sc::module]
 pub trait CommonStorageModule {
+    /// The ed25519 public key used for checking the claim signatures
     #[storage_mapper("signer")]
     fn signer(&self) -> SingleValueMapper<ManagedAddress>;
 
+    /// The secp256k1 public key used for checking the claim signatures, if any
+    #[view(getSecp256k1Signer)]
+    #[storage_mapper("secp256k1Signer")]
+    fn secp256k1_signer(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getTimekeepingMode)]
+    #[storage_mapper("timekeepingMode")]
+    fn timekeeping_mode(&self) -> SingleValueMapper<TimekeepingMode>;
+
     #[storage_mapper("firstWeekStartEpoch")]
     fn first_week_start_epoch(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getFirstWeekStartTimestamp)]
+    #[storage_mapper("firstWeekStartTimestamp")]
+    fn first_week_start_timestamp(&self) -> SingleValueMapper<Timestamp>;
+
+    #[view(getWeekLengthSeconds)]
+    #[storage_mapper("weekLengthSeconds")]
+    fn week_length_seconds(&self) -> SingleValueMapper<Timestamp>;
 }
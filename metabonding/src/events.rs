@@ -0,0 +1,141 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
+
+use crate::{
+    claim_history::ClaimHistoryEntry,
+    project::{Epoch, ProjectId},
+    timelock::{ActionId, TimelockAction},
+};
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct QueueActionEventData<M: ManagedTypeApi> {
+    pub action: TimelockAction<M>,
+    pub executable_epoch: Epoch,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_queue_action_event(
+        &self,
+        caller: &ManagedAddress,
+        action_id: ActionId,
+        action: TimelockAction<Self::Api>,
+        executable_epoch: Epoch,
+    ) {
+        let event_data = QueueActionEventData {
+            action,
+            executable_epoch,
+        };
+        self.queue_action_event(caller, action_id, &event_data);
+    }
+
+    #[inline]
+    fn emit_execute_action_event(
+        &self,
+        caller: &ManagedAddress,
+        action_id: ActionId,
+        action: &TimelockAction<Self::Api>,
+    ) {
+        self.execute_action_event(caller, action_id, action);
+    }
+
+    #[inline]
+    fn emit_cancel_action_event(
+        &self,
+        caller: &ManagedAddress,
+        action_id: ActionId,
+        action: &TimelockAction<Self::Api>,
+    ) {
+        self.cancel_action_event(caller, action_id, action);
+    }
+
+    #[inline]
+    fn emit_claim_rewards_event(
+        &self,
+        user: &ManagedAddress,
+        entries: &ManagedVec<ClaimHistoryEntry<Self::Api>>,
+    ) {
+        self.claim_rewards_event(user, entries);
+    }
+
+    #[inline]
+    fn emit_set_max_protocol_fee_event(&self, max_fee_percentage: u64) {
+        self.set_max_protocol_fee_event(max_fee_percentage);
+    }
+
+    #[inline]
+    fn emit_set_project_protocol_fee_event(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        fee_percentage: u64,
+    ) {
+        self.set_project_protocol_fee_event(project_id, fee_percentage);
+    }
+
+    #[inline]
+    fn emit_set_treasury_address_event(&self, treasury_address: &ManagedAddress) {
+        self.set_treasury_address_event(treasury_address);
+    }
+
+    #[inline]
+    fn emit_withdraw_protocol_fees_event(
+        &self,
+        treasury_address: &ManagedAddress,
+        payments: &PaymentsVec<Self::Api>,
+    ) {
+        self.withdraw_protocol_fees_event(treasury_address, payments);
+    }
+
+    #[event("queueActionEvent")]
+    fn queue_action_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] action_id: ActionId,
+        event_data: &QueueActionEventData<Self::Api>,
+    );
+
+    #[event("executeActionEvent")]
+    fn execute_action_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] action_id: ActionId,
+        action: &TimelockAction<Self::Api>,
+    );
+
+    #[event("cancelActionEvent")]
+    fn cancel_action_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] action_id: ActionId,
+        action: &TimelockAction<Self::Api>,
+    );
+
+    #[event("claimRewardsEvent")]
+    fn claim_rewards_event(
+        &self,
+        #[indexed] user: &ManagedAddress,
+        entries: &ManagedVec<ClaimHistoryEntry<Self::Api>>,
+    );
+
+    #[event("setMaxProtocolFeeEvent")]
+    fn set_max_protocol_fee_event(&self, #[indexed] max_fee_percentage: u64);
+
+    #[event("setProjectProtocolFeeEvent")]
+    fn set_project_protocol_fee_event(
+        &self,
+        #[indexed] project_id: &ProjectId<Self::Api>,
+        #[indexed] fee_percentage: u64,
+    );
+
+    #[event("setTreasuryAddressEvent")]
+    fn set_treasury_address_event(&self, #[indexed] treasury_address: &ManagedAddress);
+
+    #[event("withdrawProtocolFeesEvent")]
+    fn withdraw_protocol_fees_event(
+        &self,
+        #[indexed] treasury_address: &ManagedAddress,
+        payments: &PaymentsVec<Self::Api>,
+    );
+}
@@ -0,0 +1,20 @@
+multiversx_sc::imports!();
+
+use crate::rewards::Week;
+
+pub type GrowthProgramProjectId = u32;
+
+#[multiversx_sc::proxy]
+pub trait GrowthProgramProxy {
+    #[view(getCurrentWeek)]
+    fn get_current_week(&self) -> Week;
+
+    #[payable("*")]
+    #[endpoint(depositAdditionalRewards)]
+    fn deposit_additional_rewards(
+        &self,
+        project_id: GrowthProgramProjectId,
+        start_week: Week,
+        end_week: Week,
+    );
+}
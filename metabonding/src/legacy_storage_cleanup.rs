@@ -5,6 +5,7 @@ multiversx_sc::imports!();
 #[multiversx_sc::module]
 pub trait LegacyStorageCleanupModule:
     crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
     + crate::common_storage::CommonStorageModule
     + crate::claim_progress::ClaimProgressModule
 {
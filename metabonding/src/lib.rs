@@ -2,41 +2,94 @@
 
 multiversx_sc::imports!();
 
+use common_storage::{TimekeepingMode, MONDAY_19_02_2024_GMT_TIMESTAMP};
+
 pub mod access_control;
+pub mod bonus_pool;
 pub mod claim;
+pub mod claim_delegates;
+pub mod claim_history;
 pub mod claim_progress;
 pub mod common_storage;
+pub mod events;
+pub mod growth_program_proxy;
 pub mod legacy_storage_cleanup;
+pub mod lkmex_weighting;
 pub mod math;
+pub mod migration;
+pub mod pause;
 pub mod project;
+pub mod project_clearing;
+pub mod project_edit;
+pub mod project_opt_out;
+pub mod protocol_fee;
+pub mod proxy_settings;
 pub mod rewards;
+pub mod rollover;
+pub mod sc_whitelist;
+pub mod solvency;
+pub mod timelock;
 pub mod validation;
+pub mod vesting;
 
 #[multiversx_sc::contract]
 pub trait Metabonding:
-    multiversx_sc_modules::pause::PauseModule
+    pause::PauseModule
     + project::ProjectModule
+    + project_clearing::ProjectClearingModule
+    + project_edit::ProjectEditModule
+    + project_opt_out::ProjectOptOutModule
+    + protocol_fee::ProtocolFeeModule
+    + bonus_pool::BonusPoolModule
+    + proxy_settings::ProxySettingsModule
     + rewards::RewardsModule
+    + rollover::RolloverModule
     + claim::ClaimModule
+    + claim_delegates::ClaimDelegatesModule
+    + claim_history::ClaimHistoryModule
     + claim_progress::ClaimProgressModule
+    + vesting::VestingModule
     + access_control::AccessControlModule
     + common_storage::CommonStorageModule
     + math::MathModule
     + validation::ValidationModule
     + legacy_storage_cleanup::LegacyStorageCleanupModule
-    + sc_whitelist_module::SCWhitelistModule
+    + lkmex_weighting::LkmexWeightingModule
+    + timelock::TimelockModule
+    + migration::MigrationModule
+    + solvency::SolvencyModule
+    + events::EventsModule
+    + sc_whitelist::SCWhitelistModule
 {
     /// Arguments:
     /// - signer - public key that will be used for checking the claim signatures
-    /// - opt_first_week_start_epoch - The epoch which signals the start of week 0.
+    /// - opt_first_week_start - The epoch which signals the start of week 0.
     ///     Can also be an epoch from the past.
-    ///     By default, the current epoch on deploy will be used
+    ///     By default, the current epoch on deploy will be used.
+    ///     In timestamp mode, this is a timestamp instead, and is aligned down to the week boundaries
+    ///     used by the growth-program (i.e. periods of week_length_seconds starting from Monday, 19.02.2024 GMT)
+    /// - opt_week_length_seconds - If provided, weeks are counted based on block timestamps,
+    ///     each week lasting the given number of seconds. By default, weeks are counted in epochs.
     #[init]
-    fn init(&self, signer: ManagedAddress, opt_first_week_start_epoch: OptionalValue<u64>) {
+    fn init(
+        &self,
+        signer: ManagedAddress,
+        opt_first_week_start: OptionalValue<u64>,
+        opt_week_length_seconds: OptionalValue<project::Timestamp>,
+    ) {
         self.signer().set(&signer);
         self.set_paused(true);
+        self.timelock_delay_epochs()
+            .set_if_empty(timelock::DEFAULT_TIMELOCK_DELAY_EPOCHS);
+        self.storage_version()
+            .set(migration::CURRENT_STORAGE_VERSION);
+
+        if let OptionalValue::Some(week_length_seconds) = opt_week_length_seconds {
+            self.init_timestamp_timekeeping(opt_first_week_start, week_length_seconds);
+            return;
+        }
 
-        let first_week_start_epoch = match opt_first_week_start_epoch {
+        let first_week_start_epoch = match opt_first_week_start {
             OptionalValue::Some(epoch) => epoch,
             OptionalValue::None => self.blockchain().get_block_epoch(),
         };
@@ -44,9 +97,37 @@ pub trait Metabonding:
             .set_if_empty(first_week_start_epoch);
     }
 
-    #[only_owner]
-    #[endpoint(changeSigner)]
-    fn change_signer(&self, new_signer: ManagedAddress) {
-        self.signer().set(&new_signer);
+    /// Runs the storage migration steps registered since the last upgrade
+    #[upgrade]
+    fn upgrade(&self) {
+        self.run_migrations();
+    }
+
+    fn init_timestamp_timekeeping(
+        &self,
+        opt_first_week_start_timestamp: OptionalValue<project::Timestamp>,
+        week_length_seconds: project::Timestamp,
+    ) {
+        require!(week_length_seconds > 0, "Invalid week length");
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let first_week_start_timestamp = match opt_first_week_start_timestamp {
+            OptionalValue::Some(timestamp) => timestamp,
+            OptionalValue::None => current_timestamp,
+        };
+        require!(
+            first_week_start_timestamp >= MONDAY_19_02_2024_GMT_TIMESTAMP
+                && first_week_start_timestamp <= current_timestamp,
+            "Invalid first week start timestamp"
+        );
+
+        let aligned_first_week_start_timestamp = MONDAY_19_02_2024_GMT_TIMESTAMP
+            + (first_week_start_timestamp - MONDAY_19_02_2024_GMT_TIMESTAMP) / week_length_seconds
+                * week_length_seconds;
+
+        self.timekeeping_mode().set(TimekeepingMode::Timestamps);
+        self.week_length_seconds().set(week_length_seconds);
+        self.first_week_start_timestamp()
+            .set(aligned_first_week_start_timestamp);
     }
 }
@@ -0,0 +1,83 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{access_control::Role, claim::ClaimArgsWrapper, project::ProjectId, rewards::Week};
+
+#[derive(TypeAbi, TopEncode, TopDecode, PartialEq, Clone, Copy)]
+pub enum LkmexWeighting {
+    StakedAmount,
+    Energy,
+}
+
+#[multiversx_sc::module]
+pub trait LkmexWeightingModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+{
+    /// Sets how the LKMEX part of the project's rewards is split between users:
+    /// - StakedAmount - by the users' staked LKMEX amount (default)
+    /// - Energy - by the users' energy, relative to the total energy given in the week's checkpoint.
+    ///     Users have to claim these rewards through claimRewardsWithEnergy.
+    ///
+    /// May only be set before the project starts.
+    #[endpoint(setProjectLkmexWeighting)]
+    fn set_project_lkmex_weighting(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        weighting: LkmexWeighting,
+    ) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
+        let project = self.get_project_or_panic(&project_id);
+        let current_week = self.get_current_week();
+        require!(current_week < project.start_week, "Project already started");
+
+        self.project_lkmex_weighting(&project_id).set(weighting);
+    }
+
+    /// Weeks with energy-weighted projects need a non-zero total energy in their checkpoint
+    fn require_total_energy_if_needed(&self, week: Week, opt_total_energy: &Option<BigUint>) {
+        if let Some(total_energy) = opt_total_energy {
+            require!(*total_energy > 0, "Invalid total energy");
+            return;
+        }
+
+        for project_id in self.projects_active_in_week(week).iter() {
+            require!(
+                self.project_lkmex_weighting(&project_id).get() != LkmexWeighting::Energy,
+                "Missing total energy for week"
+            );
+        }
+    }
+
+    /// Energy-weighted rewards may only be claimed with the user's signed energy for the week,
+    /// and after the week's total energy was set through its checkpoint
+    fn has_energy_for_week(&self, claim_arg: &ClaimArgsWrapper<Self::Api>) -> bool {
+        claim_arg.opt_user_energy.is_some()
+            && !self.total_energy_for_week(claim_arg.week).is_empty()
+    }
+
+    #[view(getProjectLkmexWeighting)]
+    fn get_project_lkmex_weighting(&self, project_id: ProjectId<Self::Api>) -> LkmexWeighting {
+        self.project_lkmex_weighting(&project_id).get()
+    }
+
+    #[view(getTotalEnergyForWeek)]
+    fn get_total_energy_for_week(&self, week: Week) -> BigUint {
+        self.total_energy_for_week(week).get()
+    }
+
+    fn clear_project_lkmex_weighting(&self, project_id: &ProjectId<Self::Api>) {
+        self.project_lkmex_weighting(project_id).clear();
+    }
+
+    #[storage_mapper("projectLkmexWeighting")]
+    fn project_lkmex_weighting(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<LkmexWeighting>;
+
+    #[storage_mapper("totalEnergyForWeek")]
+    fn total_energy_for_week(&self, week: Week) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,70 @@
+multiversx_sc::imports!();
+
+use crate::timelock::DEFAULT_TIMELOCK_DELAY_EPOCHS;
+
+pub type StorageVersion = u32;
+
+/// Version 0 is the storage layout of contracts deployed before storage versioning.
+/// Each new migration step has to increment this value, and be added to run_migration_step.
+pub const CURRENT_STORAGE_VERSION: StorageVersion = 3;
+
+#[multiversx_sc::module]
+pub trait MigrationModule:
+    crate::timelock::TimelockModule
+    + crate::project::ProjectModule
+    + crate::project_clearing::ProjectClearingModule
+    + crate::vesting::VestingModule
+    + crate::lkmex_weighting::LkmexWeightingModule
+    + crate::rollover::RolloverModule
+    + crate::protocol_fee::ProtocolFeeModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::events::EventsModule
+    + crate::validation::ValidationModule
+    + crate::sc_whitelist::SCWhitelistModule
+{
+    /// Runs all the migration steps between the stored version and CURRENT_STORAGE_VERSION, in order
+    fn run_migrations(&self) {
+        let version_mapper = self.storage_version();
+        let stored_version = version_mapper.get();
+        require!(
+            stored_version <= CURRENT_STORAGE_VERSION,
+            "Cannot downgrade storage version"
+        );
+
+        for version in (stored_version + 1)..=CURRENT_STORAGE_VERSION {
+            self.run_migration_step(version);
+        }
+
+        version_mapper.set(CURRENT_STORAGE_VERSION);
+    }
+
+    fn run_migration_step(&self, version: StorageVersion) {
+        match version {
+            // contracts deployed before the timelock would otherwise have no delay
+            1 => self
+                .timelock_delay_epochs()
+                .set_if_empty(DEFAULT_TIMELOCK_DELAY_EPOCHS),
+            2 => self.index_existing_projects(),
+            3 => self.queue_existing_projects_for_clearing(),
+            _ => sc_panic!("Unknown migration step"),
+        }
+    }
+
+    fn index_existing_projects(&self) {
+        for (id, project) in self.projects().iter() {
+            self.add_to_active_weeks_index(&id, project.start_week, project.end_week);
+        }
+    }
+
+    fn queue_existing_projects_for_clearing(&self) {
+        for (id, project) in self.projects().iter() {
+            let deposit_deadline_week = self.get_deposit_deadline_week(&id, &project);
+            self.queue_project_for_clearing(&id, &project, deposit_deadline_week);
+        }
+    }
+
+    #[view(getStorageVersion)]
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<StorageVersion>;
+}
@@ -0,0 +1,53 @@
+multiversx_sc::imports!();
+
+use crate::access_control::Role;
+
+/// Pausability for the whole contract.
+/// Unlike the shared pause module, the contract may also be paused and unpaused
+/// by the addresses with the Pauser role.
+/// Uses the same storage key, so the current status is kept.
+#[multiversx_sc::module]
+pub trait PauseModule:
+    crate::access_control::AccessControlModule + crate::common_storage::CommonStorageModule
+{
+    #[inline]
+    fn is_paused(&self) -> bool {
+        self.paused_status().get()
+    }
+
+    #[inline]
+    fn not_paused(&self) -> bool {
+        !self.is_paused()
+    }
+
+    #[inline]
+    fn set_paused(&self, paused: bool) {
+        self.paused_status().set(paused);
+    }
+
+    /// May be called by the owner or any address with the Pauser role
+    #[endpoint(pause)]
+    fn pause_endpoint(&self) {
+        self.require_caller_owner_or_role(Role::Pauser);
+        self.set_paused(true);
+        self.pause_event();
+    }
+
+    /// May be called by the owner or any address with the Pauser role
+    #[endpoint(unpause)]
+    fn unpause_endpoint(&self) {
+        self.require_caller_owner_or_role(Role::Pauser);
+        self.set_paused(false);
+        self.unpause_event();
+    }
+
+    #[event("pauseContract")]
+    fn pause_event(&self);
+
+    #[event("unpauseContract")]
+    fn unpause_event(&self);
+
+    #[view(isPaused)]
+    #[storage_mapper("pause_module:paused")]
+    fn paused_status(&self) -> SingleValueMapper<bool>;
+}
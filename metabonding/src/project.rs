@@ -2,21 +2,33 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::{
-    common_storage::{EPOCHS_IN_WEEK, MAX_PERCENTAGE},
+    access_control::Role,
+    common_storage::{TimekeepingMode, EPOCHS_IN_WEEK, MAX_PERCENTAGE},
+    growth_program_proxy::GrowthProgramProjectId,
     rewards::{Week, FIRST_WEEK},
 };
 use core::convert::TryInto;
 
-pub const PROJECT_EXPIRATION_WEEKS: Week = 4;
+pub const DEFAULT_PROJECT_EXPIRATION_WEEKS: Week = 4;
+pub const MIN_PROJECT_EXPIRATION_WEEKS: Week = 1;
+pub const MAX_PROJECT_EXPIRATION_WEEKS: Week = 8;
 const MAX_PROJECT_ID_LEN: usize = 10;
-const MIN_GAS_FOR_CLEAR: u64 = 5_000_000;
+const MAX_METADATA_FIELD_LEN: usize = 256;
+pub const DESCRIPTION_HASH_LEN: usize = 32;
 static INVALID_PROJECT_ID_ERR_MSG: &[u8] = b"Invalid project ID";
 
 pub type ProjectId<M> = ManagedBuffer<M>;
 pub type ProjIdsVec<M> = ManagedVec<M, ProjectId<M>>;
-pub type ProjectAsMultiResult<M> =
-    MultiValue5<TokenIdentifier<M>, BigUint<M>, BigUint<M>, Week, Week>;
+pub type ProjectAsMultiResult<M> = MultiValue6<
+    TokenIdentifier<M>,
+    BigUint<M>,
+    BigUint<M>,
+    Week,
+    Week,
+    OptionalValue<ProjectMetadata<M>>,
+>;
 pub type Epoch = u64;
+pub type Timestamp = u64;
 
 #[derive(TypeAbi, TopEncode, TopDecode)]
 pub struct Project<M: ManagedTypeApi> {
@@ -27,10 +39,36 @@ pub struct Project<M: ManagedTypeApi> {
     pub end_week: Week,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ProjectMetadata<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub website: ManagedBuffer<M>,
+    pub description_hash: ManagedByteArray<M, DESCRIPTION_HASH_LEN>,
+    pub category: ManagedBuffer<M>,
+}
+
+/// Growth-program project that receives the leftover funds of an expired project.
+/// end_week is the last week (exclusive) of the growth-program rewards period the funds are spread over.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct GrowthProgramTarget<M: ManagedTypeApi> {
+    pub sc_address: ManagedAddress<M>,
+    pub project_id: GrowthProgramProjectId,
+    pub end_week: Week,
+}
+
+/// Leftover funds of a cleared project, awaiting to be deposited into its growth-program target
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct PendingGrowthDeposit<M: ManagedTypeApi> {
+    pub project_owner: ManagedAddress<M>,
+    pub token_id: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub target: GrowthProgramTarget<M>,
+}
+
 impl<M: ManagedTypeApi> Project<M> {
     #[inline]
-    pub fn is_expired(&self, current_week: Week) -> bool {
-        current_week > self.end_week + PROJECT_EXPIRATION_WEEKS
+    pub fn is_expired(&self, current_week: Week, expiration_weeks: Week) -> bool {
+        current_week > self.end_week + expiration_weeks
     }
 
     #[inline]
@@ -38,30 +76,41 @@ impl<M: ManagedTypeApi> Project<M> {
         self.end_week - self.start_week + 1
     }
 
-    pub fn into_multiresult(self) -> ProjectAsMultiResult<M> {
+    pub fn into_multiresult(
+        self,
+        opt_metadata: OptionalValue<ProjectMetadata<M>>,
+    ) -> ProjectAsMultiResult<M> {
         (
             self.reward_token,
             self.delegation_reward_supply,
             self.lkmex_reward_supply,
             self.start_week,
             self.end_week,
+            opt_metadata,
         )
             .into()
     }
 }
 
 #[multiversx_sc::module]
-pub trait ProjectModule: crate::common_storage::CommonStorageModule {
+pub trait ProjectModule:
+    crate::access_control::AccessControlModule + crate::common_storage::CommonStorageModule
+{
     /// Adds a new project. Arguments:
     /// - project_id: a unique ID of maximum 10 bytes
     /// - project_owner - the owner of the project. They will receive any unclaimed funds for the projects.
-    /// - reward_token - the token ID of the token given as reward
+    /// - reward_token - the token ID of the token given as reward. May also be a meta-ESDT or SFT,
+    ///     in which case the nonce is the one of the deposited rewards.
     /// - reward_supply - total supply of the reward token
     /// - start_week - the week from which the project starts producing rewards. Has to be >= 1.
     /// - duration_weeks - the duration in weeks of the project
     /// - lkmex_rewards_percentage - The percentage of the total rewards which will be given to LKMEX stakers.
     ///     Expected value range is [0, 100]
-    #[only_owner]
+    /// - opt_deposit_deadline_week - the last week in which the rewards may be deposited.
+    ///     Defaults to the project's last rewards week.
+    ///     Projects that are not funded by then are cancelled.
+    ///
+    /// May be called by the SC owner or a project manager
     #[endpoint(addProject)]
     fn add_project(
         &self,
@@ -72,7 +121,10 @@ pub trait ProjectModule: crate::common_storage::CommonStorageModule {
         start_week: Week,
         duration_weeks: Week,
         lkmex_rewards_percentage: u64,
+        opt_deposit_deadline_week: OptionalValue<Week>,
     ) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
         require!(
             reward_token.is_valid_esdt_identifier(),
             "Invalid reward token"
@@ -94,7 +146,19 @@ pub trait ProjectModule: crate::common_storage::CommonStorageModule {
             INVALID_PROJECT_ID_ERR_MSG
         );
 
+        let end_week = start_week + duration_weeks - 1;
+        let deposit_deadline_week = match opt_deposit_deadline_week {
+            OptionalValue::Some(week) => week,
+            OptionalValue::None => end_week,
+        };
+        require!(
+            deposit_deadline_week >= self.get_current_week() && deposit_deadline_week <= end_week,
+            "Invalid deposit deadline"
+        );
+
         self.project_owner(&project_id).set(&project_owner);
+        self.deposit_deadline_week(&project_id)
+            .set(deposit_deadline_week);
 
         let lkmex_reward_supply = &reward_supply * lkmex_rewards_percentage / MAX_PERCENTAGE;
         let delegation_reward_supply = &reward_supply - &lkmex_reward_supply;
@@ -104,70 +168,268 @@ pub trait ProjectModule: crate::common_storage::CommonStorageModule {
             delegation_reward_supply,
             lkmex_reward_supply,
             start_week,
-            end_week: start_week + duration_weeks - 1,
+            end_week,
         };
-        let insert_result = self.projects().insert(project_id, project);
+        self.queue_project_for_clearing(&project_id, &project, deposit_deadline_week);
+
+        require!(
+            !self.pending_growth_deposits().contains_key(&project_id)
+                && !self.reserved_project_ids().contains(&project_id),
+            "ID already in use"
+        );
+        let insert_result = self.projects().insert(project_id.clone(), project);
         require!(insert_result.is_none(), "ID already in use");
+
+        self.add_to_active_weeks_index(&project_id, start_week, end_week);
     }
 
-    /// Removes a project and gives any leftover funds to the project_owner
-    #[only_owner]
-    #[endpoint(removeProject)]
-    fn remove_project(&self, project_id: ProjectId<Self::Api>) {
+    /// Sets the display metadata of a project:
+    /// - name
+    /// - website
+    /// - description_hash - the hash of the off-chain project description and terms
+    /// - category
+    ///
+    /// May be called by the SC owner or the project's owner
+    #[endpoint(setProjectMetadata)]
+    fn set_project_metadata(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        name: ManagedBuffer,
+        website: ManagedBuffer,
+        description_hash: ManagedByteArray<DESCRIPTION_HASH_LEN>,
+        category: ManagedBuffer,
+    ) {
+        let _ = self.get_project_or_panic(&project_id);
+
+        let caller = self.blockchain().get_caller();
+        let owner = self.blockchain().get_owner_address();
+        let project_owner = self.project_owner(&project_id).get();
+        require!(
+            caller == owner || caller == project_owner,
+            "Permission denied"
+        );
+
+        for field in [&name, &website, &category] {
+            require!(
+                field.len() <= MAX_METADATA_FIELD_LEN,
+                "Metadata field too long"
+            );
+        }
+
+        let metadata = ProjectMetadata {
+            name,
+            website,
+            description_hash,
+            category,
+        };
+        self.project_metadata(&project_id).set(metadata);
+    }
+
+    /// Sets the number of weeks after which the rewards of a week may no longer be claimed.
+    /// The project also expires once this period has passed since its last rewards week.
+    /// Has to be in range [MIN_PROJECT_EXPIRATION_WEEKS, MAX_PROJECT_EXPIRATION_WEEKS].
+    /// Defaults to DEFAULT_PROJECT_EXPIRATION_WEEKS.
+    ///
+    /// May only be set before the project starts.
+    #[endpoint(setProjectExpirationWeeks)]
+    fn set_project_expiration_weeks(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        expiration_weeks: Week,
+    ) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+        require!(
+            (MIN_PROJECT_EXPIRATION_WEEKS..=MAX_PROJECT_EXPIRATION_WEEKS)
+                .contains(&expiration_weeks),
+            "Invalid expiration period"
+        );
+
         let project = self.get_project_or_panic(&project_id);
-        self.clear_and_refund_project(&project_id, &project.reward_token);
+        let current_week = self.get_current_week();
+        require!(current_week < project.start_week, "Project already started");
+
+        self.project_expiration_weeks(&project_id)
+            .set(expiration_weeks);
+
+        // the entry queued for the previous expiry week is skipped when cleared
+        self.queue_project_expiry_for_clearing(&project_id, &project);
     }
 
-    /// Clears all expired projects and sends the leftover funds to the respective project_owner.
-    /// A project is considered expired if PROJECT_EXPIRATION_WEEKS weeks
-    ///     have passed since its last rewards week
+    /// Sets the growth-program project that receives the leftover funds of the project when it is cleared,
+    /// instead of them being refunded to the project owner.
+    /// When the project is cleared, its leftover funds are kept until the owner calls depositToGrowthProgram.
+    /// The funds are deposited through depositAdditionalRewards, spread from the growth-program's next week
+    /// until end_week, so this contract has to be set as the owner of the growth-program project.
+    /// The growth-program has to be on the same shard.
+    ///
+    /// Only fungible rewards are deposited. Any other rewards are refunded when the project is cleared.
     #[only_owner]
-    #[endpoint(clearExpiredProjects)]
-    fn clear_expired_projects(&self) -> OperationCompletionStatus {
-        let mut prev_token = TokenIdentifier::from_esdt_bytes(&[]);
-        let mut prev_id = ProjectId::<Self::Api>::new();
-        let mut clear_prev_id = false;
-        let current_week = self.get_current_week();
+    #[endpoint(setGrowthProgramTarget)]
+    fn set_growth_program_target(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        sc_address: ManagedAddress,
+        growth_project_id: GrowthProgramProjectId,
+        end_week: Week,
+    ) {
+        let _ = self.get_project_or_panic(&project_id);
+        require!(
+            self.blockchain().is_smart_contract(&sc_address),
+            "Invalid growth-program address"
+        );
 
-        // can only clear on next step, otherwise we'd lose the map's internal links
-        for (id, project) in self.projects().iter() {
-            let gas_left = self.blockchain().get_gas_left();
-            if gas_left < MIN_GAS_FOR_CLEAR {
-                return OperationCompletionStatus::InterruptedBeforeOutOfGas;
-            }
+        let growth_current_week: Week = self
+            .growth_program_proxy(sc_address.clone())
+            .get_current_week()
+            .execute_on_dest_context();
+        require!(end_week > growth_current_week + 1, "Invalid end week");
+
+        self.growth_program_target(&project_id)
+            .set(GrowthProgramTarget {
+                sc_address,
+                project_id: growth_project_id,
+                end_week,
+            });
+    }
 
-            if clear_prev_id {
-                self.clear_and_refund_project(&prev_id, &prev_token);
-                clear_prev_id = false;
-            }
+    /// Leftover funds are refunded to the project owner again.
+    #[only_owner]
+    #[endpoint(removeGrowthProgramTarget)]
+    fn remove_growth_program_target(&self, project_id: ProjectId<Self::Api>) {
+        let _ = self.get_project_or_panic(&project_id);
 
-            if project.is_expired(current_week) {
-                prev_token = project.reward_token;
-                prev_id = id;
-                clear_prev_id = true;
-            }
-        }
+        self.growth_program_target(&project_id).clear();
+    }
 
-        if clear_prev_id {
-            self.clear_and_refund_project(&prev_id, &prev_token);
+    /// Deposits the leftover funds of a cleared project into its growth-program target.
+    /// The part that cannot be deposited is refunded to the project owner.
+    ///
+    /// If the growth-program rejects the deposit, the funds can be refunded through refundGrowthProgramDeposit.
+    #[only_owner]
+    #[endpoint(depositToGrowthProgram)]
+    fn deposit_to_growth_program(&self, project_id: ProjectId<Self::Api>) {
+        let opt_pending_deposit = self.pending_growth_deposits().remove(&project_id);
+        require!(opt_pending_deposit.is_some(), "No pending deposit");
+
+        let pending_deposit = unsafe { opt_pending_deposit.unwrap_unchecked() };
+        let refund_amount = self.deposit_into_growth_program(
+            &pending_deposit.target,
+            &pending_deposit.token_id,
+            pending_deposit.amount,
+        );
+        if refund_amount > 0 {
+            self.send().direct_esdt(
+                &pending_deposit.project_owner,
+                &pending_deposit.token_id,
+                0,
+                &refund_amount,
+            );
         }
+    }
+
+    /// Refunds the leftover funds of a cleared project to the project owner,
+    /// instead of depositing them into the growth-program.
+    /// May be called by either the SC owner or the project owner.
+    #[endpoint(refundGrowthProgramDeposit)]
+    fn refund_growth_program_deposit(&self, project_id: ProjectId<Self::Api>) {
+        let opt_pending_deposit = self.pending_growth_deposits().remove(&project_id);
+        require!(opt_pending_deposit.is_some(), "No pending deposit");
+
+        let pending_deposit = unsafe { opt_pending_deposit.unwrap_unchecked() };
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == pending_deposit.project_owner
+                || caller == self.blockchain().get_owner_address(),
+            "Permission denied"
+        );
+
+        self.send().direct_esdt(
+            &pending_deposit.project_owner,
+            &pending_deposit.token_id,
+            0,
+            &pending_deposit.amount,
+        );
+    }
 
-        OperationCompletionStatus::Completed
+    /// Queues the project for clearing, both for when it would be cancelled and for when it expires
+    fn queue_project_for_clearing(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        deposit_deadline_week: Week,
+    ) {
+        let _ = self
+            .projects_clearable_from_week(deposit_deadline_week + 1)
+            .insert(project_id.clone());
+        self.queue_project_expiry_for_clearing(project_id, project);
     }
 
-    fn clear_and_refund_project(
+    fn queue_project_expiry_for_clearing(
         &self,
         project_id: &ProjectId<Self::Api>,
-        token_id: &TokenIdentifier,
+        project: &Project<Self::Api>,
     ) {
-        let project_owner = self.project_owner(project_id).take();
-        let leftover_funds = self.leftover_project_funds(project_id).take();
+        let expiration_weeks = self.get_project_expiration_weeks(project_id);
+        let _ = self
+            .projects_clearable_from_week(project.end_week + expiration_weeks + 1)
+            .insert(project_id.clone());
+    }
 
-        let _ = self.projects().remove(project_id);
+    /// Deposits the funds into the growth-program project, spread from its next week until the target's end_week.
+    /// Only a multiple of the number of weeks is deposited, so the growth-program has no surplus to send back.
+    ///
+    /// Returns the amount that could not be deposited
+    fn deposit_into_growth_program(
+        &self,
+        target: &GrowthProgramTarget<Self::Api>,
+        token_id: &TokenIdentifier,
+        amount: BigUint,
+    ) -> BigUint {
+        let growth_current_week: Week = self
+            .growth_program_proxy(target.sc_address.clone())
+            .get_current_week()
+            .execute_on_dest_context();
+        let start_week = growth_current_week + 1;
+        if start_week >= target.end_week {
+            return amount;
+        }
 
-        if leftover_funds > 0 {
-            self.send()
-                .direct_esdt(&project_owner, token_id, 0, &leftover_funds);
+        let nr_weeks = (target.end_week - start_week) as u32;
+        let deposit_amount = &amount / nr_weeks * nr_weeks;
+        if deposit_amount == 0 {
+            return amount;
+        }
+
+        let remaining_amount = amount - &deposit_amount;
+        self.growth_program_proxy(target.sc_address.clone())
+            .deposit_additional_rewards(target.project_id, start_week, target.end_week)
+            .with_esdt_transfer(EsdtTokenPayment::new(token_id.clone(), 0, deposit_amount))
+            .execute_on_dest_context::<IgnoreValue>();
+
+        remaining_amount
+    }
+
+    fn add_to_active_weeks_index(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        start_week: Week,
+        end_week: Week,
+    ) {
+        for week in start_week..=end_week {
+            let _ = self
+                .projects_active_in_week(week)
+                .insert(project_id.clone());
+        }
+    }
+
+    fn remove_from_active_weeks_index(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        start_week: Week,
+        end_week: Week,
+    ) {
+        for week in start_week..=end_week {
+            let _ = self.projects_active_in_week(week).swap_remove(project_id);
         }
     }
 
@@ -176,27 +438,148 @@ pub trait ProjectModule: crate::common_storage::CommonStorageModule {
         self.get_all_project_ids().into()
     }
 
+    /// Cancelled projects are not included
     fn get_all_project_ids(&self) -> ProjIdsVec<Self::Api> {
+        let current_week = self.get_current_week();
         let mut all_ids = ManagedVec::new();
         for id in self.projects().keys() {
-            all_ids.push(id);
+            if !self.is_project_cancelled(&id, current_week) {
+                all_ids.push(id);
+            }
         }
 
         all_ids
     }
 
+    /// Returns the projects whose rewards were not deposited yet, and which are not cancelled.
+    /// The results are pairs of:
+    /// - project_id
+    /// - deposit_deadline_week
+    #[view(getProjectsAwaitingFunding)]
+    fn get_projects_awaiting_funding(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ProjectId<Self::Api>, Week>> {
+        let current_week = self.get_current_week();
+        let mut result = MultiValueEncoded::new();
+        for (id, project) in self.projects().iter() {
+            if self.rewards_deposited(&id).get() {
+                continue;
+            }
+
+            let deposit_deadline_week = self.get_deposit_deadline_week(&id, &project);
+            if current_week <= deposit_deadline_week {
+                result.push((id, deposit_deadline_week).into());
+            }
+        }
+
+        result
+    }
+
+    #[view(getProjectRewardNonce)]
+    fn get_project_reward_nonce(&self, project_id: ProjectId<Self::Api>) -> u64 {
+        self.project_reward_nonce(&project_id).get()
+    }
+
+    #[view(getProjectDepositDeadline)]
+    fn get_project_deposit_deadline(&self, project_id: ProjectId<Self::Api>) -> Week {
+        let project = self.get_project_or_panic(&project_id);
+
+        self.get_deposit_deadline_week(&project_id, &project)
+    }
+
+    fn is_project_cancelled(&self, project_id: &ProjectId<Self::Api>, current_week: Week) -> bool {
+        if self.rewards_deposited(project_id).get() {
+            return false;
+        }
+
+        match self.projects().get(project_id) {
+            Some(project) => current_week > self.get_deposit_deadline_week(project_id, &project),
+            None => false,
+        }
+    }
+
+    fn is_project_expired(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        current_week: Week,
+    ) -> bool {
+        let expiration_weeks = self.get_project_expiration_weeks(project_id);
+
+        project.is_expired(current_week, expiration_weeks)
+    }
+
+    #[view(getProjectExpirationWeeks)]
+    fn get_project_expiration_weeks_view(&self, project_id: ProjectId<Self::Api>) -> Week {
+        self.get_project_expiration_weeks(&project_id)
+    }
+
+    fn get_project_expiration_weeks(&self, project_id: &ProjectId<Self::Api>) -> Week {
+        let mapper = self.project_expiration_weeks(project_id);
+        if mapper.is_empty() {
+            return DEFAULT_PROJECT_EXPIRATION_WEEKS;
+        }
+
+        mapper.get()
+    }
+
+    /// Projects added before deposit deadlines were introduced may be funded until their last rewards week
+    fn get_deposit_deadline_week(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+    ) -> Week {
+        let deadline_mapper = self.deposit_deadline_week(project_id);
+        if deadline_mapper.is_empty() {
+            return project.end_week;
+        }
+
+        deadline_mapper.get()
+    }
+
+    #[view(getGrowthProgramTarget)]
+    fn get_growth_program_target(
+        &self,
+        project_id: ProjectId<Self::Api>,
+    ) -> OptionalValue<GrowthProgramTarget<Self::Api>> {
+        let mapper = self.growth_program_target(&project_id);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    /// Returns the leftover funds of a cleared project that await to be deposited into the growth-program
+    #[view(getPendingGrowthProgramDeposit)]
+    fn get_pending_growth_program_deposit(
+        &self,
+        project_id: ProjectId<Self::Api>,
+    ) -> OptionalValue<PendingGrowthDeposit<Self::Api>> {
+        self.pending_growth_deposits().get(&project_id).into()
+    }
+
     /// Returns a project by ID. The results are, in order:
     /// - reward_token
     /// - delegation_reward_supply
     /// - lkmex_reward_supply
     /// - start_week
     /// - end_week
+    /// - metadata - optional, only if it was set for the project
     #[view(getProjectById)]
     fn get_project_by_id(
         &self,
         project_id: ProjectId<Self::Api>,
     ) -> ProjectAsMultiResult<Self::Api> {
-        self.get_project_or_panic(&project_id).into_multiresult()
+        let project = self.get_project_or_panic(&project_id);
+        let metadata_mapper = self.project_metadata(&project_id);
+        let opt_metadata = if !metadata_mapper.is_empty() {
+            OptionalValue::Some(metadata_mapper.get())
+        } else {
+            OptionalValue::None
+        };
+
+        project.into_multiresult(opt_metadata)
     }
 
     fn get_project_or_panic(&self, project_id: &ProjectId<Self::Api>) -> Project<Self::Api> {
@@ -207,30 +590,93 @@ pub trait ProjectModule: crate::common_storage::CommonStorageModule {
 
     #[view(getCurrentWeek)]
     fn get_current_week(&self) -> Week {
-        let first_week_start_epoch = self.first_week_start_epoch().get();
-        let current_epoch = self.blockchain().get_block_epoch();
+        let weeks_passed = match self.timekeeping_mode().get() {
+            TimekeepingMode::Epochs => {
+                let first_week_start_epoch = self.first_week_start_epoch().get();
+                let current_epoch = self.blockchain().get_block_epoch();
+
+                (current_epoch - first_week_start_epoch) / EPOCHS_IN_WEEK
+            }
+            TimekeepingMode::Timestamps => {
+                let first_week_start_timestamp = self.first_week_start_timestamp().get();
+                let week_length_seconds = self.week_length_seconds().get();
+                let current_timestamp = self.blockchain().get_block_timestamp();
+
+                (current_timestamp - first_week_start_timestamp) / week_length_seconds
+            }
+        };
 
         // will never overflow usize
-        unsafe {
-            ((current_epoch - first_week_start_epoch) / EPOCHS_IN_WEEK)
-                .try_into()
-                .unwrap_unchecked()
-        }
+        unsafe { weeks_passed.try_into().unwrap_unchecked() }
     }
 
     #[storage_mapper("projects")]
     fn projects(&self) -> MapMapper<ProjectId<Self::Api>, Project<Self::Api>>;
 
+    /// Index of the projects giving rewards in each week
+    #[storage_mapper("projectsActiveInWeek")]
+    fn projects_active_in_week(&self, week: Week) -> UnorderedSetMapper<ProjectId<Self::Api>>;
+
+    /// Queue of the projects that may be cleared starting with the given week
+    #[storage_mapper("projectsClearableFromWeek")]
+    fn projects_clearable_from_week(&self, week: Week) -> UnorderedSetMapper<ProjectId<Self::Api>>;
+
+    /// All the clearing queues before this week were processed
+    #[view(getClearCursorWeek)]
+    #[storage_mapper("clearCursorWeek")]
+    fn clear_cursor_week(&self) -> SingleValueMapper<Week>;
+
+    #[storage_mapper("projectExpirationWeeks")]
+    fn project_expiration_weeks(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<Week>;
+
+    #[storage_mapper("growthProgramTarget")]
+    fn growth_program_target(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<GrowthProgramTarget<Self::Api>>;
+
+    #[storage_mapper("pendingGrowthDeposits")]
+    fn pending_growth_deposits(
+        &self,
+    ) -> MapMapper<ProjectId<Self::Api>, PendingGrowthDeposit<Self::Api>>;
+
+    /// IDs of cleared projects that may not be reused yet,
+    /// as users still have rewards vesting for them
+    #[storage_mapper("reservedProjectIds")]
+    fn reserved_project_ids(&self) -> UnorderedSetMapper<ProjectId<Self::Api>>;
+
+    #[proxy]
+    fn growth_program_proxy(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> crate::growth_program_proxy::Proxy<Self::Api>;
+
     #[storage_mapper("projectOwner")]
     fn project_owner(&self, project_id: &ProjectId<Self::Api>)
         -> SingleValueMapper<ManagedAddress>;
 
+    #[storage_mapper("projectMetadata")]
+    fn project_metadata(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<ProjectMetadata<Self::Api>>;
+
     #[storage_mapper("leftoverProjectFunds")]
     fn leftover_project_funds(
         &self,
         project_id: &ProjectId<Self::Api>,
     ) -> SingleValueMapper<BigUint>;
 
+    /// Nonce of the deposited rewards. Always 0 for fungible tokens.
+    #[storage_mapper("projectRewardNonce")]
+    fn project_reward_nonce(&self, project_id: &ProjectId<Self::Api>) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("depositDeadlineWeek")]
+    fn deposit_deadline_week(&self, project_id: &ProjectId<Self::Api>) -> SingleValueMapper<Week>;
+
     #[storage_mapper("rewardsDeposited")]
     fn rewards_deposited(&self, project_id: &ProjectId<Self::Api>) -> SingleValueMapper<bool>;
 }
@@ -0,0 +1,115 @@
+multiversx_sc::imports!();
+
+use crate::project::{PendingGrowthDeposit, ProjectId};
+
+const MIN_GAS_FOR_CLEAR: u64 = 5_000_000;
+
+#[multiversx_sc::module]
+pub trait ProjectClearingModule:
+    crate::project::ProjectModule
+    + crate::vesting::VestingModule
+    + crate::lkmex_weighting::LkmexWeightingModule
+    + crate::rollover::RolloverModule
+    + crate::protocol_fee::ProtocolFeeModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::events::EventsModule
+{
+    /// Clears the expired and cancelled projects and sends the leftover funds to the respective project_owner.
+    /// If the project has a growth-program target, the funds are kept for depositToGrowthProgram instead.
+    /// A project is considered expired if its expiration period (in weeks)
+    ///     has passed since its last rewards week.
+    /// A project is considered cancelled if its rewards were not deposited until its deposit deadline.
+    ///
+    /// Projects are queued by the week from which they may be cleared,
+    /// so only the projects that are due are processed. If the gas runs out,
+    /// the next call continues from where the previous one stopped.
+    ///
+    /// May be called by anyone
+    #[endpoint(clearExpiredProjects)]
+    fn clear_expired_projects(&self) -> OperationCompletionStatus {
+        let current_week = self.get_current_week();
+        let cursor_mapper = self.clear_cursor_week();
+        let mut week = cursor_mapper.get();
+        while week <= current_week {
+            let mut queue_mapper = self.projects_clearable_from_week(week);
+            while !queue_mapper.is_empty() {
+                let gas_left = self.blockchain().get_gas_left();
+                if gas_left < MIN_GAS_FOR_CLEAR {
+                    cursor_mapper.set(week);
+                    return OperationCompletionStatus::InterruptedBeforeOutOfGas;
+                }
+
+                let project_id = queue_mapper.get_by_index(queue_mapper.len());
+                let _ = queue_mapper.swap_remove(&project_id);
+
+                // the project might have been removed, or might only be due for a later week
+                let opt_project = self.projects().get(&project_id);
+                if let Some(project) = opt_project {
+                    if self.is_project_expired(&project_id, &project, current_week)
+                        || self.is_project_cancelled(&project_id, current_week)
+                    {
+                        self.clear_and_refund_project(&project_id, &project.reward_token);
+                    }
+                }
+            }
+
+            week += 1;
+        }
+
+        cursor_mapper.set(week);
+
+        OperationCompletionStatus::Completed
+    }
+
+    fn clear_and_refund_project(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        token_id: &TokenIdentifier,
+    ) {
+        let project_owner = self.project_owner(project_id).take();
+        let leftover_funds = self.leftover_project_funds(project_id).take();
+        let reward_nonce = self.project_reward_nonce(project_id).take();
+        let target_mapper = self.growth_program_target(project_id);
+        let opt_growth_program_target = if !target_mapper.is_empty() {
+            Some(target_mapper.take())
+        } else {
+            None
+        };
+        self.project_metadata(project_id).clear();
+        self.deposit_deadline_week(project_id).clear();
+        self.project_expiration_weeks(project_id).clear();
+        self.rewards_deposited(project_id).clear();
+        self.clear_project_vesting(project_id);
+        self.clear_project_protocol_fee(project_id);
+        self.clear_project_lkmex_weighting(project_id);
+
+        if let Some(project) = self.projects().remove(project_id) {
+            self.remove_from_active_weeks_index(project_id, project.start_week, project.end_week);
+            self.clear_project_rollover(project_id, &project);
+        }
+
+        if leftover_funds == 0 {
+            return;
+        }
+
+        // the growth-program only accepts fungible tokens
+        match opt_growth_program_target {
+            Some(target) if reward_nonce == 0 => {
+                let pending_deposit = PendingGrowthDeposit {
+                    project_owner,
+                    token_id: token_id.clone(),
+                    amount: leftover_funds,
+                    target,
+                };
+                let _ = self
+                    .pending_growth_deposits()
+                    .insert(project_id.clone(), pending_deposit);
+            }
+            _ => {
+                self.send()
+                    .direct_esdt(&project_owner, token_id, reward_nonce, &leftover_funds);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+multiversx_sc::imports!();
+
+use crate::{
+    access_control::Role,
+    common_storage::MAX_PERCENTAGE,
+    project::{Project, ProjectId},
+    rewards::{Week, FIRST_WEEK},
+};
+
+#[multiversx_sc::module]
+pub trait ProjectEditModule:
+    crate::project::ProjectModule
+    + crate::vesting::VestingModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+{
+    /// Changes the start week, duration and LKMEX rewards percentage of a project.
+    /// The total reward supply stays the same. Arguments have the same meaning as for addProject.
+    ///
+    /// May only be called before the project starts. Since weeks before the start week
+    /// give no rewards, no claims can have been made against the project until then.
+    ///
+    /// May be called by the SC owner or a project manager
+    #[endpoint(editProject)]
+    fn edit_project(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        start_week: Week,
+        duration_weeks: Week,
+        lkmex_rewards_percentage: u64,
+    ) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
+        let old_project = self.get_project_or_panic(&project_id);
+        let current_week = self.get_current_week();
+        require!(
+            current_week < old_project.start_week,
+            "Project already started"
+        );
+        require!(
+            start_week >= FIRST_WEEK && duration_weeks > 0,
+            "Invalid duration"
+        );
+        require!(
+            lkmex_rewards_percentage <= MAX_PERCENTAGE,
+            "Invalid percentage"
+        );
+
+        let end_week = start_week + duration_weeks - 1;
+        let deposit_deadline_week = self.get_deposit_deadline_week(&project_id, &old_project);
+        let new_deposit_deadline_week = core::cmp::min(deposit_deadline_week, end_week);
+        require!(
+            new_deposit_deadline_week >= current_week,
+            "Invalid deposit deadline"
+        );
+
+        let reward_supply =
+            &old_project.delegation_reward_supply + &old_project.lkmex_reward_supply;
+        let lkmex_reward_supply = &reward_supply * lkmex_rewards_percentage / MAX_PERCENTAGE;
+        let delegation_reward_supply = reward_supply - &lkmex_reward_supply;
+
+        let mut project = old_project;
+        self.remove_from_active_weeks_index(&project_id, project.start_week, project.end_week);
+
+        project.delegation_reward_supply = delegation_reward_supply;
+        project.lkmex_reward_supply = lkmex_reward_supply;
+        project.start_week = start_week;
+        project.end_week = end_week;
+
+        // the entries queued for the previous weeks are skipped when cleared
+        self.add_to_active_weeks_index(&project_id, start_week, end_week);
+        self.queue_project_for_clearing(&project_id, &project, new_deposit_deadline_week);
+        if new_deposit_deadline_week != deposit_deadline_week {
+            self.deposit_deadline_week(&project_id)
+                .set(new_deposit_deadline_week);
+        }
+
+        let _ = self.projects().insert(project_id, project);
+    }
+
+    /// Extends a funded project by the given number of weeks. The payment has to be the project's reward token,
+    /// with the same nonce as the deposited rewards,
+    /// and the amount has to keep the weekly rewards unchanged, i.e. the current weekly reward
+    /// multiplied by the number of extra weeks. The required amount can be queried through getProjectExtensionCost.
+    ///
+    /// May only be called until the project's last rewards week.
+    /// Projects with a vesting schedule can't be extended, as their vesting starts after the last rewards week.
+    ///
+    /// May be called by the SC owner or a project manager
+    #[payable("*")]
+    #[endpoint(extendProject)]
+    fn extend_project(&self, project_id: ProjectId<Self::Api>, extra_weeks: Week) {
+        let payment = self.call_value().single_esdt();
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
+        let mut project = self.get_project_or_panic(&project_id);
+        require!(
+            self.rewards_deposited(&project_id).get(),
+            "Rewards not deposited"
+        );
+        require!(
+            self.project_vesting(&project_id).is_empty(),
+            "Project has a vesting schedule"
+        );
+
+        let current_week = self.get_current_week();
+        require!(current_week <= project.end_week, "Project already ended");
+        require!(extra_weeks > 0, "Invalid duration");
+        require!(
+            project.reward_token == payment.token_identifier
+                && self.project_reward_nonce(&project_id).get() == payment.token_nonce,
+            "Invalid payment token"
+        );
+
+        let (extra_delegation_supply, extra_lkmex_supply) =
+            self.get_extension_supply(&project, extra_weeks);
+        require!(
+            &extra_delegation_supply + &extra_lkmex_supply == payment.amount,
+            "Invalid amount"
+        );
+
+        let old_end_week = project.end_week;
+        project.delegation_reward_supply += extra_delegation_supply;
+        project.lkmex_reward_supply += extra_lkmex_supply;
+        project.end_week += extra_weeks;
+
+        self.add_to_active_weeks_index(&project_id, old_end_week + 1, project.end_week);
+        self.queue_project_expiry_for_clearing(&project_id, &project);
+        self.leftover_project_funds(&project_id)
+            .update(|leftover| *leftover += payment.amount);
+
+        let _ = self.projects().insert(project_id, project);
+    }
+
+    #[view(getProjectExtensionCost)]
+    fn get_project_extension_cost(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        extra_weeks: Week,
+    ) -> BigUint {
+        let project = self.get_project_or_panic(&project_id);
+        let (extra_delegation_supply, extra_lkmex_supply) =
+            self.get_extension_supply(&project, extra_weeks);
+
+        extra_delegation_supply + extra_lkmex_supply
+    }
+
+    /// Returns the delegation and LKMEX supplies needed for the extra weeks,
+    /// so that the weekly rewards remain the same
+    fn get_extension_supply(
+        &self,
+        project: &Project<Self::Api>,
+        extra_weeks: Week,
+    ) -> (BigUint, BigUint) {
+        let duration_weeks = project.get_duration_in_weeks() as u32;
+        let extra_weeks = extra_weeks as u32;
+        let extra_delegation_supply =
+            &project.delegation_reward_supply / duration_weeks * extra_weeks;
+        let extra_lkmex_supply = &project.lkmex_reward_supply / duration_weeks * extra_weeks;
+
+        (extra_delegation_supply, extra_lkmex_supply)
+    }
+}
@@ -0,0 +1,56 @@
+multiversx_sc::imports!();
+
+use crate::project::ProjectId;
+
+#[multiversx_sc::module]
+pub trait ProjectOptOutModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+{
+    /// Adds the given projects to the caller's opt-out list.
+    /// Rewards of these projects are skipped when claiming, and the weeks are marked as claimed for them.
+    /// The forgone rewards remain with the project.
+    #[endpoint(optOutOfProjects)]
+    fn opt_out_of_projects(&self, project_ids: MultiValueEncoded<ProjectId<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let mut mapper = self.opted_out_projects(&caller);
+        for project_id in project_ids {
+            let _ = self.get_project_or_panic(&project_id);
+            let _ = mapper.insert(project_id);
+        }
+    }
+
+    /// Removes the given projects from the caller's opt-out list.
+    /// Rewards for weeks claimed while opted out may not be claimed anymore.
+    #[endpoint(optInToProjects)]
+    fn opt_in_to_projects(&self, project_ids: MultiValueEncoded<ProjectId<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let mut mapper = self.opted_out_projects(&caller);
+        for project_id in project_ids {
+            let _ = mapper.swap_remove(&project_id);
+        }
+    }
+
+    #[view(getOptedOutProjects)]
+    fn get_opted_out_projects(
+        &self,
+        user: ManagedAddress,
+    ) -> MultiValueEncoded<ProjectId<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for project_id in self.opted_out_projects(&user).iter() {
+            result.push(project_id);
+        }
+
+        result
+    }
+
+    #[inline]
+    fn is_opted_out(&self, user: &ManagedAddress, project_id: &ProjectId<Self::Api>) -> bool {
+        self.opted_out_projects(user).contains(project_id)
+    }
+
+    #[storage_mapper("optedOutProjects")]
+    fn opted_out_projects(&self, user: &ManagedAddress)
+        -> UnorderedSetMapper<ProjectId<Self::Api>>;
+}
@@ -0,0 +1,142 @@
+multiversx_sc::imports!();
+
+use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
+
+use crate::{common_storage::MAX_PERCENTAGE, project::ProjectId};
+
+#[multiversx_sc::module]
+pub trait ProtocolFeeModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::events::EventsModule
+{
+    /// Sets the maximum protocol fee percentage any project may have. Expected value range is [0, 100].
+    /// Projects with a higher fee are charged the maximum instead.
+    #[only_owner]
+    #[endpoint(setMaxProtocolFeePercentage)]
+    fn set_max_protocol_fee_percentage(&self, max_fee_percentage: u64) {
+        require!(max_fee_percentage <= MAX_PERCENTAGE, "Invalid percentage");
+
+        self.max_protocol_fee_percentage().set(max_fee_percentage);
+        self.emit_set_max_protocol_fee_event(max_fee_percentage);
+    }
+
+    /// Sets the percentage of each claim payout of the project that is kept as protocol fee.
+    ///
+    /// May only be set before the project starts, so all of its weeks are charged the same fee.
+    #[only_owner]
+    #[endpoint(setProjectProtocolFee)]
+    fn set_project_protocol_fee(&self, project_id: ProjectId<Self::Api>, fee_percentage: u64) {
+        let project = self.get_project_or_panic(&project_id);
+        let current_week = self.get_current_week();
+        require!(current_week < project.start_week, "Project already started");
+        require!(
+            fee_percentage <= self.max_protocol_fee_percentage().get(),
+            "Fee above maximum"
+        );
+
+        self.project_protocol_fee_percentage(&project_id)
+            .set(fee_percentage);
+        self.emit_set_project_protocol_fee_event(&project_id, fee_percentage);
+    }
+
+    #[only_owner]
+    #[endpoint(setTreasuryAddress)]
+    fn set_treasury_address(&self, treasury_address: ManagedAddress) {
+        self.treasury_address().set(&treasury_address);
+        self.emit_set_treasury_address_event(&treasury_address);
+    }
+
+    /// Sends all the collected protocol fees to the treasury. May only be called by the treasury address.
+    #[endpoint(withdrawProtocolFees)]
+    fn withdraw_protocol_fees(&self) -> PaymentsVec<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let treasury_address = self.treasury_address().get();
+        require!(caller == treasury_address, "Permission denied");
+
+        let mut payments = PaymentsVec::new();
+        let mut token_ids = self.treasury_token_ids();
+        for (token_id, token_nonce) in token_ids.iter() {
+            let balance = self.treasury_balance(&token_id, token_nonce).take();
+            if balance > 0 {
+                payments.push(EsdtTokenPayment::new(token_id, token_nonce, balance));
+            }
+        }
+        token_ids.clear();
+
+        require!(!payments.is_empty(), "No fees to withdraw");
+
+        self.send().direct_multi(&caller, &payments);
+        self.emit_withdraw_protocol_fees_event(&caller, &payments);
+
+        payments
+    }
+
+    /// Returns the protocol fee percentage charged for the project, capped by the current maximum
+    fn get_protocol_fee_percentage(&self, project_id: &ProjectId<Self::Api>) -> u64 {
+        let fee_percentage = self.project_protocol_fee_percentage(project_id).get();
+        let max_fee_percentage = self.max_protocol_fee_percentage().get();
+
+        core::cmp::min(fee_percentage, max_fee_percentage)
+    }
+
+    fn add_to_treasury(&self, token_id: &TokenIdentifier, token_nonce: u64, amount: &BigUint) {
+        if amount == &0 {
+            return;
+        }
+
+        self.treasury_balance(token_id, token_nonce)
+            .update(|balance| *balance += amount);
+        let _ = self
+            .treasury_token_ids()
+            .insert((token_id.clone(), token_nonce));
+    }
+
+    fn clear_project_protocol_fee(&self, project_id: &ProjectId<Self::Api>) {
+        self.project_protocol_fee_percentage(project_id).clear();
+    }
+
+    #[view(getProjectProtocolFee)]
+    fn get_project_protocol_fee(&self, project_id: ProjectId<Self::Api>) -> u64 {
+        self.get_protocol_fee_percentage(&project_id)
+    }
+
+    /// Returns the collected protocol fees, as triplets of token_id, token_nonce and amount
+    #[view(getTreasuryBalances)]
+    fn get_treasury_balances(
+        &self,
+    ) -> MultiValueEncoded<MultiValue3<TokenIdentifier, u64, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for (token_id, token_nonce) in self.treasury_token_ids().iter() {
+            let balance = self.treasury_balance(&token_id, token_nonce).get();
+            result.push((token_id, token_nonce, balance).into());
+        }
+
+        result
+    }
+
+    #[view(getMaxProtocolFeePercentage)]
+    #[storage_mapper("maxProtocolFeePercentage")]
+    fn max_protocol_fee_percentage(&self) -> SingleValueMapper<u64>;
+
+    #[view(getTreasuryAddress)]
+    #[storage_mapper("treasuryAddress")]
+    fn treasury_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("projectProtocolFeePercentage")]
+    fn project_protocol_fee_percentage(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("treasuryBalance")]
+    fn treasury_balance(
+        &self,
+        token_id: &TokenIdentifier,
+        token_nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("treasuryTokenIds")]
+    fn treasury_token_ids(&self) -> UnorderedSetMapper<(TokenIdentifier, u64)>;
+}
@@ -0,0 +1,149 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
+
+use crate::{access_control::Role, rewards::Week};
+
+pub type ProxyClaimStatsAsMultiResult<M> = MultiValue3<TokenIdentifier<M>, BigUint<M>, BigUint<M>>;
+
+/// Proxies without settings are enabled by default
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct ProxySettings<M: ManagedTypeApi> {
+    pub label: ManagedBuffer<M>,
+    pub enabled: bool,
+}
+
+#[multiversx_sc::module]
+pub trait ProxySettingsModule:
+    crate::access_control::AccessControlModule + crate::common_storage::CommonStorageModule
+{
+    #[endpoint(setProxySettings)]
+    fn set_proxy_settings(&self, proxy: ManagedAddress, label: ManagedBuffer, enabled: bool) {
+        self.require_caller_owner_or_role(Role::WhitelistManager);
+
+        self.proxy_settings(&proxy)
+            .set(ProxySettings { label, enabled });
+    }
+
+    /// Sets the maximum amount of the given token the proxy may claim each week.
+    /// A weekly_cap of 0 removes the cap.
+    #[endpoint(setProxyWeeklyCap)]
+    fn set_proxy_weekly_cap(
+        &self,
+        proxy: ManagedAddress,
+        token_id: TokenIdentifier,
+        weekly_cap: BigUint,
+    ) {
+        self.require_caller_owner_or_role(Role::WhitelistManager);
+
+        let mapper = self.proxy_weekly_cap(&proxy, &token_id);
+        if weekly_cap == 0 {
+            mapper.clear();
+        } else {
+            mapper.set(&weekly_cap);
+        }
+    }
+
+    #[view(getProxySettings)]
+    fn get_proxy_settings(&self, proxy: ManagedAddress) -> OptionalValue<ProxySettings<Self::Api>> {
+        let mapper = self.proxy_settings(&proxy);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    #[view(getProxyWeeklyCap)]
+    fn get_proxy_weekly_cap(&self, proxy: ManagedAddress, token_id: TokenIdentifier) -> BigUint {
+        self.proxy_weekly_cap(&proxy, &token_id).get()
+    }
+
+    /// Returns the amounts claimed through the given proxy. The results are triplets of:
+    /// - token_id
+    /// - claimed_in_week - the amount claimed during the given week
+    /// - total_claimed
+    #[view(getProxyClaimStats)]
+    fn get_proxy_claim_stats(
+        &self,
+        proxy: ManagedAddress,
+        week: Week,
+    ) -> MultiValueEncoded<ProxyClaimStatsAsMultiResult<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.proxy_claimed_tokens(&proxy).iter() {
+            let claimed_in_week = self.proxy_weekly_claimed(&proxy, week, &token_id).get();
+            let total_claimed = self.proxy_total_claimed(&proxy, &token_id).get();
+            result.push((token_id, claimed_in_week, total_claimed).into());
+        }
+
+        result
+    }
+
+    fn require_proxy_enabled(&self, proxy: &ManagedAddress) {
+        let mapper = self.proxy_settings(proxy);
+        if mapper.is_empty() {
+            return;
+        }
+
+        let settings = mapper.get();
+        require!(settings.enabled, "Proxy is disabled");
+    }
+
+    fn update_proxy_claimed_amounts(
+        &self,
+        proxy: &ManagedAddress,
+        current_week: Week,
+        rewards: &PaymentsVec<Self::Api>,
+    ) {
+        for payment in rewards {
+            let weekly_claimed_mapper =
+                self.proxy_weekly_claimed(proxy, current_week, &payment.token_identifier);
+            let new_weekly_claimed = weekly_claimed_mapper.get() + &payment.amount;
+
+            let weekly_cap_mapper = self.proxy_weekly_cap(proxy, &payment.token_identifier);
+            if !weekly_cap_mapper.is_empty() {
+                require!(
+                    new_weekly_claimed <= weekly_cap_mapper.get(),
+                    "Proxy weekly claim cap exceeded"
+                );
+            }
+
+            weekly_claimed_mapper.set(&new_weekly_claimed);
+            self.proxy_total_claimed(proxy, &payment.token_identifier)
+                .update(|total| *total += &payment.amount);
+            let _ = self
+                .proxy_claimed_tokens(proxy)
+                .insert(payment.token_identifier);
+        }
+    }
+
+    #[storage_mapper("proxySettings")]
+    fn proxy_settings(&self, proxy: &ManagedAddress)
+        -> SingleValueMapper<ProxySettings<Self::Api>>;
+
+    #[storage_mapper("proxyWeeklyCap")]
+    fn proxy_weekly_cap(
+        &self,
+        proxy: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("proxyWeeklyClaimed")]
+    fn proxy_weekly_claimed(
+        &self,
+        proxy: &ManagedAddress,
+        week: Week,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("proxyTotalClaimed")]
+    fn proxy_total_claimed(
+        &self,
+        proxy: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("proxyClaimedTokens")]
+    fn proxy_claimed_tokens(&self, proxy: &ManagedAddress) -> UnorderedSetMapper<TokenIdentifier>;
+}
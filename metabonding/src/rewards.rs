@@ -2,7 +2,9 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::{
+    access_control::Role,
     claim::ClaimArgsWrapper,
+    lkmex_weighting::LkmexWeighting,
     project::{Project, ProjectId},
     validation::Signature,
 };
@@ -10,6 +12,7 @@ use crate::{
 pub type Week = usize;
 pub type PrettyRewards<M> =
     MultiValueEncoded<M, MultiValue3<ProjectId<M>, TokenIdentifier<M>, BigUint<M>>>;
+pub type CheckpointEntry<M> = MultiValue4<Week, BigUint<M>, BigUint<M>, Option<BigUint<M>>>;
 
 pub const FIRST_WEEK: usize = 1;
 
@@ -30,44 +33,98 @@ impl<M: ManagedTypeApi> Default for RewardsCheckpoint<M> {
 
 #[multiversx_sc::module]
 pub trait RewardsModule:
-    multiversx_sc_modules::pause::PauseModule
+    crate::pause::PauseModule
     + crate::project::ProjectModule
     + crate::access_control::AccessControlModule
     + crate::common_storage::CommonStorageModule
     + crate::math::MathModule
     + crate::validation::ValidationModule
+    + crate::lkmex_weighting::LkmexWeightingModule
+    + crate::rollover::RolloverModule
 {
     /// Adds a rewards checkpoint for the given Week. Only one checkpoint per week is allowed.
     /// Checkpoints have to be added in order, and only if the current week is equal to the given week
     ///     or the given week is in the past.
-    /// Only the SC owner or the signer may add checkpoints. Arguments:
+    /// Only the SC owner or a checkpoint operator may add checkpoints. Arguments:
     /// - week - the week for which the checkpoint is added
     /// - total_delegation_supply - The total amount of staked EGLD in the Delegation SC
     /// - total_lkmex_staked - The total LKMEX staked in the Metabonding-Staking SC
+    /// - opt_total_energy - The total energy of the users, used by projects with energy weighting
     #[endpoint(addRewardsCheckpoint)]
     fn add_rewards_checkpoint(
         &self,
         week: Week,
         total_delegation_supply: BigUint,
         total_lkmex_staked: BigUint,
+        opt_total_energy: OptionalValue<BigUint>,
     ) {
-        self.require_caller_owner_or_signer();
+        self.require_caller_owner_or_role(Role::CheckpointOperator);
+
+        let current_week = self.get_current_week();
+        self.add_checkpoint_for_week(
+            current_week,
+            week,
+            total_delegation_supply,
+            total_lkmex_staked,
+            opt_total_energy.into_option(),
+        );
+    }
+
+    /// Adds multiple rewards checkpoints at once, with the same rules as addRewardsCheckpoint.
+    /// If any of the entries is invalid, the whole batch is rejected.
+    /// Arguments are entries of:
+    /// - week
+    /// - total_delegation_supply
+    /// - total_lkmex_staked
+    /// - opt_total_energy - None if not used
+    #[endpoint(addRewardsCheckpoints)]
+    fn add_rewards_checkpoints(&self, checkpoints: MultiValueEncoded<CheckpointEntry<Self::Api>>) {
+        self.require_caller_owner_or_role(Role::CheckpointOperator);
+        require!(!checkpoints.is_empty(), "No checkpoints");
 
-        let last_checkpoint_week = self.get_last_checkpoint_week();
         let current_week = self.get_current_week();
+        for entry in checkpoints {
+            let (week, total_delegation_supply, total_lkmex_staked, opt_total_energy) =
+                entry.into_tuple();
+            self.add_checkpoint_for_week(
+                current_week,
+                week,
+                total_delegation_supply,
+                total_lkmex_staked,
+                opt_total_energy,
+            );
+        }
+    }
+
+    fn add_checkpoint_for_week(
+        &self,
+        current_week: Week,
+        week: Week,
+        total_delegation_supply: BigUint,
+        total_lkmex_staked: BigUint,
+        opt_total_energy: Option<BigUint>,
+    ) {
+        let last_checkpoint_week = self.get_last_checkpoint_week();
         require!(
             week == last_checkpoint_week + 1 && week <= current_week,
             "Invalid checkpoint week"
         );
+        self.require_total_energy_if_needed(week, &opt_total_energy);
 
         let checkpoint = RewardsCheckpoint {
             total_delegation_supply,
             total_lkmex_staked,
         };
+        self.process_rollover(current_week, week);
         self.rewards_checkpoints().push(&checkpoint);
+
+        if let Some(total_energy) = opt_total_energy {
+            self.total_energy_for_week(week).set(&total_energy);
+        }
     }
 
     /// Deposits rewards for the given project. The full amount has to be deposited all at once.
+    /// Meta-ESDT and SFT rewards are accepted as well, and are paid out with the deposited nonce.
     #[payable("*")]
     #[endpoint(depositRewards)]
     fn deposit_rewards(&self, project_id: ProjectId<Self::Api>) {
@@ -76,7 +133,7 @@ pub trait RewardsModule:
             "Rewards already deposited"
         );
 
-        let (payment_token, payment_amount) = self.call_value().single_fungible_esdt();
+        let payment = self.call_value().single_esdt();
         let project = self.get_project_or_panic(&project_id);
 
         let caller = self.blockchain().get_caller();
@@ -87,21 +144,33 @@ pub trait RewardsModule:
         );
 
         let current_week = self.get_current_week();
-        require!(!project.is_expired(current_week), "Project is expired");
+        require!(
+            !self.is_project_expired(&project_id, &project, current_week),
+            "Project is expired"
+        );
+        require!(
+            current_week <= self.get_deposit_deadline_week(&project_id, &project),
+            "Deposit deadline passed"
+        );
 
         let total_reward_supply = project.lkmex_reward_supply + project.delegation_reward_supply;
         require!(
-            project.reward_token == payment_token,
+            project.reward_token == payment.token_identifier,
             "Invalid payment token"
         );
-        require!(total_reward_supply == payment_amount, "Invalid amount");
+        require!(total_reward_supply == payment.amount, "Invalid amount");
 
         self.leftover_project_funds(&project_id)
             .set(&total_reward_supply);
         self.rewards_deposited(&project_id).set(true);
+        if payment.token_nonce != 0 {
+            self.project_reward_nonce(&project_id)
+                .set(payment.token_nonce);
+        }
     }
 
-    /// Gets rewards for the given week, assuming the user has the given staked EGLD and LKMEX amounts.
+    /// Gets rewards for the given week, assuming the user has the given staked EGLD and LKMEX amounts,
+    /// and optionally the given energy, for projects with energy weighting.
     /// Returned results are pairs of:
     /// - project_id
     /// - project_reward_token
@@ -112,6 +181,7 @@ pub trait RewardsModule:
         week: Week,
         user_delegation_amount: BigUint,
         user_lkmex_staked_amount: BigUint,
+        opt_user_energy: OptionalValue<BigUint>,
     ) -> PrettyRewards<Self::Api> {
         let checkpoint = self.rewards_checkpoints().get(week);
         let current_week = self.get_current_week();
@@ -119,12 +189,20 @@ pub trait RewardsModule:
             week,
             user_delegation_amount,
             user_lkmex_staked_amount,
+            opt_user_energy: opt_user_energy.into_option(),
             checkpoint,
-            signature: Signature::default(),
+            signature: Signature::new(),
         };
 
         let mut rewards_pretty = MultiValueEncoded::new();
-        for (id, project) in self.projects().iter() {
+        let projects_mapper = self.projects();
+        for id in self.projects_active_in_week(week).iter() {
+            let opt_project = projects_mapper.get(&id);
+            if opt_project.is_none() {
+                continue;
+            }
+
+            let project = unsafe { opt_project.unwrap_unchecked() };
             let opt_weekly_reward =
                 self.get_weekly_reward_for_project(&id, &project, current_week, &claim_arg);
 
@@ -143,17 +221,11 @@ pub trait RewardsModule:
         current_week: Week,
         claim_arg: &ClaimArgsWrapper<Self::Api>,
     ) -> Option<BigUint> {
-        if !self.is_in_range(claim_arg.week, project.start_week, project.end_week) {
-            return None;
-        }
-        if !self.rewards_deposited(project_id).get() {
-            return None;
-        }
-        if project.is_expired(current_week) {
+        if !self.is_project_claimable_for_week(project_id, project, claim_arg.week, current_week) {
             return None;
         }
 
-        let reward_amount = self.calculate_reward_amount(project, claim_arg);
+        let reward_amount = self.calculate_reward_amount(project_id, project, claim_arg);
         if reward_amount > 0 {
             Some(reward_amount)
         } else {
@@ -161,26 +233,61 @@ pub trait RewardsModule:
         }
     }
 
+    fn is_project_claimable_for_week(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        week: Week,
+        current_week: Week,
+    ) -> bool {
+        if !self.is_in_range(week, project.start_week, project.end_week) {
+            return false;
+        }
+        if !self.rewards_deposited(project_id).get() {
+            return false;
+        }
+
+        // each week may only be claimed during the project's expiration period
+        let expiration_weeks = self.get_project_expiration_weeks(project_id);
+        current_week <= week + expiration_weeks
+            && !project.is_expired(current_week, expiration_weeks)
+    }
+
     fn calculate_reward_amount(
         &self,
+        project_id: &ProjectId<Self::Api>,
         project: &Project<Self::Api>,
         claim_arg: &ClaimArgsWrapper<Self::Api>,
     ) -> BigUint {
         let project_duration_weeks = project.get_duration_in_weeks() as u32;
+        let (rolled_over_delegation, rolled_over_lkmex) =
+            self.get_rolled_over_supply(project_id, project, claim_arg.week);
         let rewards_supply_per_week_delegation =
-            &project.delegation_reward_supply / project_duration_weeks;
-        let rewards_supply_per_week_lkmex = &project.lkmex_reward_supply / project_duration_weeks;
+            &project.delegation_reward_supply / project_duration_weeks + rolled_over_delegation;
+        let rewards_supply_per_week_lkmex =
+            &project.lkmex_reward_supply / project_duration_weeks + rolled_over_lkmex;
 
         let rewards_delegation = self.calculate_ratio(
             &rewards_supply_per_week_delegation,
             &claim_arg.user_delegation_amount,
             &claim_arg.checkpoint.total_delegation_supply,
         );
-        let rewards_lkmex = self.calculate_ratio(
-            &rewards_supply_per_week_lkmex,
-            &claim_arg.user_lkmex_staked_amount,
-            &claim_arg.checkpoint.total_lkmex_staked,
-        );
+        let rewards_lkmex = match self.project_lkmex_weighting(project_id).get() {
+            LkmexWeighting::StakedAmount => self.calculate_ratio(
+                &rewards_supply_per_week_lkmex,
+                &claim_arg.user_lkmex_staked_amount,
+                &claim_arg.checkpoint.total_lkmex_staked,
+            ),
+            // claims skip these projects without energy, so this only applies to the rewards view
+            LkmexWeighting::Energy => match &claim_arg.opt_user_energy {
+                Some(user_energy) => self.calculate_ratio(
+                    &rewards_supply_per_week_lkmex,
+                    user_energy,
+                    &self.total_energy_for_week(claim_arg.week).get(),
+                ),
+                None => BigUint::zero(),
+            },
+        };
 
         rewards_delegation + rewards_lkmex
     }
@@ -0,0 +1,201 @@
+multiversx_sc::imports!();
+
+use crate::{
+    access_control::Role,
+    project::{Project, ProjectId},
+    rewards::Week,
+};
+
+#[multiversx_sc::module]
+pub trait RolloverModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+{
+    /// Enables or disables the rollover of unclaimed rewards for the given project.
+    /// With rollover enabled, once a week can no longer be claimed, its unclaimed rewards are
+    /// split equally between the project's weeks that have no checkpoint yet.
+    ///
+    /// May only be set before the project starts.
+    #[endpoint(setProjectRollover)]
+    fn set_project_rollover(&self, project_id: ProjectId<Self::Api>, enabled: bool) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
+        let project = self.get_project_or_panic(&project_id);
+        let current_week = self.get_current_week();
+        require!(current_week < project.start_week, "Project already started");
+
+        let mut mapper = self.rollover_projects();
+        if enabled {
+            let _ = mapper.insert(project_id);
+        } else {
+            let _ = mapper.swap_remove(&project_id);
+        }
+    }
+
+    #[view(isProjectRolloverEnabled)]
+    fn is_project_rollover_enabled(&self, project_id: ProjectId<Self::Api>) -> bool {
+        self.rollover_projects().contains(&project_id)
+    }
+
+    #[view(getRolledOverAmount)]
+    fn get_rolled_over_amount(&self, project_id: ProjectId<Self::Api>, week: Week) -> BigUint {
+        self.rolled_over_amount(&project_id, week).get()
+    }
+
+    /// Rolls over the unclaimed rewards of all the weeks whose claim window closed,
+    /// into the weeks starting from next_checkpoint_week
+    fn process_rollover(&self, current_week: Week, next_checkpoint_week: Week) {
+        let mut rollover_projects = self.rollover_projects();
+        let mut project_ids = ManagedVec::<Self::Api, ProjectId<Self::Api>>::new();
+        for project_id in rollover_projects.iter() {
+            project_ids.push(project_id);
+        }
+
+        for project_id in &project_ids {
+            let opt_project = self.projects().get(&project_id);
+            if opt_project.is_none() {
+                let _ = rollover_projects.swap_remove(&project_id);
+                continue;
+            }
+
+            let project = unsafe { opt_project.unwrap_unchecked() };
+            if !self.rewards_deposited(&project_id).get() {
+                continue;
+            }
+
+            let expiration_weeks = self.get_project_expiration_weeks(&project_id);
+            if current_week <= expiration_weeks {
+                continue;
+            }
+
+            let last_closed_week = current_week - expiration_weeks - 1;
+            self.process_project_rollover(
+                &project_id,
+                &project,
+                last_closed_week,
+                next_checkpoint_week,
+            );
+        }
+    }
+
+    fn process_project_rollover(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        last_closed_week: Week,
+        next_checkpoint_week: Week,
+    ) {
+        let last_rolled_over_mapper = self.last_rolled_over_week(project_id);
+        let first_week = core::cmp::max(last_rolled_over_mapper.get() + 1, project.start_week);
+        let last_week = core::cmp::min(last_closed_week, project.end_week);
+        if first_week > last_week {
+            return;
+        }
+
+        for week in first_week..=last_week {
+            let weekly_budget = self.get_weekly_budget(project_id, project, week);
+            let claimed_amount = self.weekly_claimed_amount(project_id, week).take();
+            self.rolled_over_amount(project_id, week).clear();
+            if claimed_amount >= weekly_budget {
+                continue;
+            }
+
+            let first_target_week = core::cmp::max(next_checkpoint_week, week + 1);
+            if first_target_week > project.end_week {
+                continue;
+            }
+
+            let nr_target_weeks = (project.end_week - first_target_week + 1) as u32;
+            let amount_per_week = (weekly_budget - claimed_amount) / nr_target_weeks;
+            if amount_per_week == 0 {
+                continue;
+            }
+
+            for target_week in first_target_week..=project.end_week {
+                self.rolled_over_amount(project_id, target_week)
+                    .update(|amount| *amount += &amount_per_week);
+            }
+        }
+
+        last_rolled_over_mapper.set(last_week);
+    }
+
+    fn get_weekly_budget(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        week: Week,
+    ) -> BigUint {
+        let project_duration_weeks = project.get_duration_in_weeks() as u32;
+        let base_budget = &project.delegation_reward_supply / project_duration_weeks
+            + &project.lkmex_reward_supply / project_duration_weeks;
+
+        base_budget + self.rolled_over_amount(project_id, week).get()
+    }
+
+    /// Splits the rolled over amount for the given week between the delegation and LKMEX pools,
+    /// proportional to the project's supplies. Results are, in order:
+    /// - delegation part
+    /// - LKMEX part
+    fn get_rolled_over_supply(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        week: Week,
+    ) -> (BigUint, BigUint) {
+        let rolled_over_amount = self.rolled_over_amount(project_id, week).get();
+        if rolled_over_amount == 0 {
+            return (BigUint::zero(), BigUint::zero());
+        }
+
+        let total_supply = &project.delegation_reward_supply + &project.lkmex_reward_supply;
+        let lkmex_part = &rolled_over_amount * &project.lkmex_reward_supply / total_supply;
+        let delegation_part = rolled_over_amount - &lkmex_part;
+
+        (delegation_part, lkmex_part)
+    }
+
+    fn add_weekly_claimed_amount(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        week: Week,
+        amount: &BigUint,
+    ) {
+        self.weekly_claimed_amount(project_id, week)
+            .update(|claimed| *claimed += amount);
+    }
+
+    fn clear_project_rollover(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+    ) {
+        let _ = self.rollover_projects().swap_remove(project_id);
+        self.last_rolled_over_week(project_id).clear();
+        for week in project.start_week..=project.end_week {
+            self.rolled_over_amount(project_id, week).clear();
+            self.weekly_claimed_amount(project_id, week).clear();
+        }
+    }
+
+    #[storage_mapper("rolloverProjects")]
+    fn rollover_projects(&self) -> UnorderedSetMapper<ProjectId<Self::Api>>;
+
+    #[storage_mapper("rolledOverAmount")]
+    fn rolled_over_amount(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        week: Week,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("weeklyClaimedAmount")]
+    fn weekly_claimed_amount(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+        week: Week,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("lastRolledOverWeek")]
+    fn last_rolled_over_week(&self, project_id: &ProjectId<Self::Api>) -> SingleValueMapper<Week>;
+}
@@ -0,0 +1,20 @@
+multiversx_sc::imports!();
+
+/// Whitelist of the proxy contracts that may claim on behalf of users.
+/// Unlike the shared whitelist module, it exposes no owner endpoints, as changes have to go through
+/// the timelocked addProxyToWhitelist and removeProxyFromWhitelist actions.
+/// Uses the same storage key, so existing entries are kept.
+#[multiversx_sc::module]
+pub trait SCWhitelistModule {
+    #[view(isSCAddressWhitelisted)]
+    fn is_sc_address_whitelisted(&self, address: ManagedAddress) -> bool {
+        self.sc_whitelist_addresses().contains(&address)
+    }
+
+    fn require_sc_address_whitelisted(&self, address: &ManagedAddress) {
+        self.sc_whitelist_addresses().require_whitelisted(address);
+    }
+
+    #[storage_mapper("scWhitelistAddresses")]
+    fn sc_whitelist_addresses(&self) -> WhitelistMapper<ManagedAddress>;
+}
@@ -0,0 +1,174 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type TokenSolvencyAsMultiResult<M> =
+    MultiValue4<TokenIdentifier<M>, u64, BigUint<M>, BigUint<M>>;
+
+#[derive(ManagedVecItem)]
+pub struct TokenSolvency<M: ManagedTypeApi> {
+    pub token_id: TokenIdentifier<M>,
+    pub token_nonce: u64,
+    pub owed_amount: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait SolvencyModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::vesting::VestingModule
+    + crate::protocol_fee::ProtocolFeeModule
+    + crate::bonus_pool::BonusPoolModule
+    + crate::math::MathModule
+    + crate::events::EventsModule
+{
+    /// Returns, for each reward token, the amount owed to projects and users and the actual SC balance.
+    /// The results are, in order:
+    /// - token_id
+    /// - token_nonce - 0 for fungible tokens
+    /// - owed_amount - the sum of the leftover funds of all the projects using this token,
+    ///     plus the rewards still held in vesting positions, the collected protocol fees,
+    ///     the undistributed bonus pools and the pending growth-program deposits
+    /// - sc_balance
+    #[view(getSolvencyReport)]
+    fn get_solvency_report(&self) -> MultiValueEncoded<TokenSolvencyAsMultiResult<Self::Api>> {
+        let mut owed_per_token = ManagedVec::<Self::Api, TokenSolvency<Self::Api>>::new();
+        for (id, project) in self.projects().iter() {
+            let leftover_funds = self.leftover_project_funds(&id).get();
+            let reward_nonce = self.project_reward_nonce(&id).get();
+            self.add_owed_amount(
+                &mut owed_per_token,
+                project.reward_token,
+                reward_nonce,
+                leftover_funds,
+            );
+        }
+        for (token_id, token_nonce) in self.vesting_token_ids().iter() {
+            let vesting_amount = self.total_vesting_amount(&token_id, token_nonce).get();
+            self.add_owed_amount(&mut owed_per_token, token_id, token_nonce, vesting_amount);
+        }
+        for (token_id, token_nonce) in self.treasury_token_ids().iter() {
+            let treasury_balance = self.treasury_balance(&token_id, token_nonce).get();
+            self.add_owed_amount(&mut owed_per_token, token_id, token_nonce, treasury_balance);
+        }
+        for token_id in self.bonus_pool_token_ids().iter() {
+            let bonus_amount = self.total_bonus_pools_remaining(&token_id).get();
+            self.add_owed_amount(&mut owed_per_token, token_id, 0, bonus_amount);
+        }
+        for pending_deposit in self.pending_growth_deposits().values() {
+            self.add_owed_amount(
+                &mut owed_per_token,
+                pending_deposit.token_id,
+                0,
+                pending_deposit.amount,
+            );
+        }
+
+        let mut report = MultiValueEncoded::new();
+        for entry in &owed_per_token {
+            let sc_balance = self.get_sc_token_balance(&entry.token_id, entry.token_nonce);
+            report.push(
+                (
+                    entry.token_id,
+                    entry.token_nonce,
+                    entry.owed_amount,
+                    sc_balance,
+                )
+                    .into(),
+            );
+        }
+
+        report
+    }
+
+    /// Returns the amount of the given token held by the SC above what is owed to projects and users.
+    /// The token nonce defaults to 0, i.e. a fungible token.
+    #[view(getTokenSurplus)]
+    fn get_token_surplus(
+        &self,
+        token_id: TokenIdentifier,
+        opt_token_nonce: OptionalValue<u64>,
+    ) -> BigUint {
+        let token_nonce = opt_token_nonce.into_option().unwrap_or_default();
+        let owed_amount = self.get_owed_amount_for_token(&token_id, token_nonce);
+        let sc_balance = self.get_sc_token_balance(&token_id, token_nonce);
+        if sc_balance > owed_amount {
+            sc_balance - owed_amount
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    /// Sends the surplus of the given token to the SC owner.
+    /// Only the amount above what is owed to projects and users may be withdrawn.
+    #[only_owner]
+    #[endpoint(withdrawSurplus)]
+    fn withdraw_surplus(
+        &self,
+        token_id: TokenIdentifier,
+        opt_token_nonce: OptionalValue<u64>,
+    ) -> BigUint {
+        let token_nonce = opt_token_nonce.into_option().unwrap_or_default();
+        let surplus = self.get_token_surplus(token_id.clone(), OptionalValue::Some(token_nonce));
+        require!(surplus > 0, "No surplus to withdraw");
+
+        let owner = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&owner, &token_id, token_nonce, &surplus);
+
+        surplus
+    }
+
+    fn add_owed_amount(
+        &self,
+        owed_per_token: &mut ManagedVec<TokenSolvency<Self::Api>>,
+        token_id: TokenIdentifier,
+        token_nonce: u64,
+        amount: BigUint,
+    ) {
+        let opt_index = owed_per_token
+            .iter()
+            .position(|entry| entry.token_id == token_id && entry.token_nonce == token_nonce);
+        match opt_index {
+            Some(index) => {
+                let mut entry = owed_per_token.get(index);
+                entry.owed_amount += amount;
+                let _ = owed_per_token.set(index, &entry);
+            }
+            None => owed_per_token.push(TokenSolvency {
+                token_id,
+                token_nonce,
+                owed_amount: amount,
+            }),
+        }
+    }
+
+    fn get_owed_amount_for_token(&self, token_id: &TokenIdentifier, token_nonce: u64) -> BigUint {
+        let mut owed_amount = self.total_vesting_amount(token_id, token_nonce).get();
+        owed_amount += self.treasury_balance(token_id, token_nonce).get();
+        if token_nonce == 0 {
+            owed_amount += self.total_bonus_pools_remaining(token_id).get();
+            for pending_deposit in self.pending_growth_deposits().values() {
+                if &pending_deposit.token_id == token_id {
+                    owed_amount += pending_deposit.amount;
+                }
+            }
+        }
+        for (id, project) in self.projects().iter() {
+            if &project.reward_token == token_id
+                && self.project_reward_nonce(&id).get() == token_nonce
+            {
+                owed_amount += self.leftover_project_funds(&id).get();
+            }
+        }
+
+        owed_amount
+    }
+
+    fn get_sc_token_balance(&self, token_id: &TokenIdentifier, token_nonce: u64) -> BigUint {
+        self.blockchain().get_sc_balance(
+            &EgldOrEsdtTokenIdentifier::esdt(token_id.clone()),
+            token_nonce,
+        )
+    }
+}
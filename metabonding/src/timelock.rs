@@ -0,0 +1,251 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    access_control::Role,
+    project::{Epoch, ProjectId},
+};
+
+pub const DEFAULT_TIMELOCK_DELAY_EPOCHS: Epoch = 7;
+pub const MIN_TIMELOCK_DELAY_EPOCHS: Epoch = 1;
+static INVALID_ACTION_ID_ERR_MSG: &[u8] = b"Invalid action ID";
+
+pub type ActionId = u64;
+pub type QueuedActionAsMultiResult<M> = MultiValue3<ActionId, TimelockAction<M>, Epoch>;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub enum TimelockAction<M: ManagedTypeApi> {
+    RemoveProject(ProjectId<M>),
+    ChangeSigner(ManagedAddress<M>),
+    AddProxyToWhitelist(ManagedAddress<M>),
+    RemoveProxyFromWhitelist(ManagedAddress<M>),
+    ChangeSecp256k1Signer(ManagedBuffer<M>),
+    DecreaseTimelockDelay(Epoch),
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct QueuedAction<M: ManagedTypeApi> {
+    pub action: TimelockAction<M>,
+    pub executable_epoch: Epoch,
+}
+
+#[multiversx_sc::module]
+pub trait TimelockModule:
+    crate::project::ProjectModule
+    + crate::project_clearing::ProjectClearingModule
+    + crate::vesting::VestingModule
+    + crate::lkmex_weighting::LkmexWeightingModule
+    + crate::rollover::RolloverModule
+    + crate::protocol_fee::ProtocolFeeModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+    + crate::events::EventsModule
+    + crate::validation::ValidationModule
+    + crate::sc_whitelist::SCWhitelistModule
+{
+    /// Sets the minimum number of epochs that have to pass between queueing an action
+    /// and being able to execute it. Does not affect already queued actions.
+    ///
+    /// Increases take effect immediately. Decreases are timelocked actions themselves,
+    /// in which case the ID of the queued action is returned.
+    #[only_owner]
+    #[endpoint(setTimelockDelay)]
+    fn set_timelock_delay(&self, delay_epochs: Epoch) -> OptionalValue<ActionId> {
+        require!(
+            delay_epochs >= MIN_TIMELOCK_DELAY_EPOCHS,
+            "Invalid timelock delay"
+        );
+
+        if delay_epochs >= self.timelock_delay_epochs().get() {
+            self.timelock_delay_epochs().set(delay_epochs);
+
+            return OptionalValue::None;
+        }
+
+        let action_id = self.queue_action(TimelockAction::DecreaseTimelockDelay(delay_epochs));
+
+        OptionalValue::Some(action_id)
+    }
+
+    /// Queues the removal of a project. Once executed, any leftover funds are given to the project_owner
+    #[endpoint(removeProject)]
+    fn remove_project(&self, project_id: ProjectId<Self::Api>) -> ActionId {
+        let _ = self.get_project_or_panic(&project_id);
+
+        self.queue_action(TimelockAction::RemoveProject(project_id))
+    }
+
+    /// Queues a change of the public key used for checking the claim signatures
+    #[endpoint(changeSigner)]
+    fn change_signer(&self, new_signer: ManagedAddress) -> ActionId {
+        self.queue_action(TimelockAction::ChangeSigner(new_signer))
+    }
+
+    /// Queues a change of the secp256k1 public key used for checking the claim signatures.
+    /// Both a compressed and an uncompressed key are accepted. An empty key removes the secp256k1 signer.
+    /// The ed25519 signer remains in use for ed25519 signatures.
+    #[endpoint(changeSecp256k1Signer)]
+    fn change_secp256k1_signer(&self, new_signer: ManagedBuffer) -> ActionId {
+        if !new_signer.is_empty() {
+            self.require_valid_secp256k1_key(&new_signer);
+        }
+
+        self.queue_action(TimelockAction::ChangeSecp256k1Signer(new_signer))
+    }
+
+    #[endpoint(addProxyToWhitelist)]
+    fn add_proxy_to_whitelist(&self, address: ManagedAddress) -> ActionId {
+        self.queue_action(TimelockAction::AddProxyToWhitelist(address))
+    }
+
+    #[endpoint(removeProxyFromWhitelist)]
+    fn remove_proxy_from_whitelist(&self, address: ManagedAddress) -> ActionId {
+        self.queue_action(TimelockAction::RemoveProxyFromWhitelist(address))
+    }
+
+    /// Executes a previously queued action, once its delay has passed.
+    /// Requires the same permissions as queueing the action.
+    #[endpoint(executeTimelockAction)]
+    fn execute_timelock_action(&self, action_id: ActionId) {
+        let queued_action = self.get_queued_action_or_panic(action_id);
+        self.require_can_queue_action(&queued_action.action);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(
+            current_epoch >= queued_action.executable_epoch,
+            "Action is still timelocked"
+        );
+
+        let _ = self.queued_actions().remove(&action_id);
+
+        match &queued_action.action {
+            TimelockAction::RemoveProject(project_id) => {
+                let project = self.get_project_or_panic(project_id);
+                self.clear_and_refund_project(project_id, &project.reward_token);
+            }
+            TimelockAction::ChangeSigner(new_signer) => {
+                self.signer().set(new_signer);
+            }
+            TimelockAction::ChangeSecp256k1Signer(new_signer) => {
+                if new_signer.is_empty() {
+                    self.secp256k1_signer().clear();
+                } else {
+                    self.secp256k1_signer().set(new_signer);
+                }
+            }
+            TimelockAction::AddProxyToWhitelist(address) => {
+                self.sc_whitelist_addresses().add(address);
+            }
+            TimelockAction::RemoveProxyFromWhitelist(address) => {
+                self.sc_whitelist_addresses().remove(address);
+            }
+            TimelockAction::DecreaseTimelockDelay(delay_epochs) => {
+                self.timelock_delay_epochs().set(delay_epochs);
+            }
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.emit_execute_action_event(&caller, action_id, &queued_action.action);
+    }
+
+    /// Cancels a queued action. Only the SC owner may cancel actions.
+    #[only_owner]
+    #[endpoint(cancelTimelockAction)]
+    fn cancel_timelock_action(&self, action_id: ActionId) {
+        let queued_action = self
+            .queued_actions()
+            .remove(&action_id)
+            .unwrap_or_else(|| sc_panic!(INVALID_ACTION_ID_ERR_MSG));
+
+        let caller = self.blockchain().get_caller();
+        self.emit_cancel_action_event(&caller, action_id, &queued_action.action);
+    }
+
+    /// Returns all the queued actions. The results are triplets of:
+    /// - action_id
+    /// - action
+    /// - executable_epoch
+    #[view(getQueuedActions)]
+    fn get_queued_actions(&self) -> MultiValueEncoded<QueuedActionAsMultiResult<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for (action_id, queued_action) in self.queued_actions().iter() {
+            result.push(
+                (
+                    action_id,
+                    queued_action.action,
+                    queued_action.executable_epoch,
+                )
+                    .into(),
+            );
+        }
+
+        result
+    }
+
+    #[view(getQueuedAction)]
+    fn get_queued_action(&self, action_id: ActionId) -> QueuedActionAsMultiResult<Self::Api> {
+        let queued_action = self.get_queued_action_or_panic(action_id);
+
+        (
+            action_id,
+            queued_action.action,
+            queued_action.executable_epoch,
+        )
+            .into()
+    }
+
+    fn queue_action(&self, action: TimelockAction<Self::Api>) -> ActionId {
+        self.require_can_queue_action(&action);
+
+        let action_id = self.last_action_id().get() + 1;
+        self.last_action_id().set(action_id);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let executable_epoch = current_epoch + self.timelock_delay_epochs().get();
+        let queued_action = QueuedAction {
+            action: action.clone(),
+            executable_epoch,
+        };
+        let _ = self.queued_actions().insert(action_id, queued_action);
+
+        let caller = self.blockchain().get_caller();
+        self.emit_queue_action_event(&caller, action_id, action, executable_epoch);
+
+        action_id
+    }
+
+    fn require_can_queue_action(&self, action: &TimelockAction<Self::Api>) {
+        match action {
+            TimelockAction::RemoveProject(_) => {
+                self.require_caller_owner_or_role(Role::ProjectManager)
+            }
+            TimelockAction::ChangeSigner(_)
+            | TimelockAction::ChangeSecp256k1Signer(_)
+            | TimelockAction::DecreaseTimelockDelay(_) => {
+                let caller = self.blockchain().get_caller();
+                let owner = self.blockchain().get_owner_address();
+                require!(caller == owner, "Permission denied");
+            }
+            TimelockAction::AddProxyToWhitelist(_)
+            | TimelockAction::RemoveProxyFromWhitelist(_) => {
+                self.require_caller_owner_or_role(Role::WhitelistManager)
+            }
+        }
+    }
+
+    fn get_queued_action_or_panic(&self, action_id: ActionId) -> QueuedAction<Self::Api> {
+        self.queued_actions()
+            .get(&action_id)
+            .unwrap_or_else(|| sc_panic!(INVALID_ACTION_ID_ERR_MSG))
+    }
+
+    #[view(getTimelockDelay)]
+    #[storage_mapper("timelockDelayEpochs")]
+    fn timelock_delay_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("lastActionId")]
+    fn last_action_id(&self) -> SingleValueMapper<ActionId>;
+
+    #[storage_mapper("queuedActions")]
+    fn queued_actions(&self) -> MapMapper<ActionId, QueuedAction<Self::Api>>;
+}
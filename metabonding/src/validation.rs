@@ -7,31 +7,105 @@ use crate::{
 };
 use multiversx_sc::api::ED25519_SIGNATURE_BYTE_LEN;
 
-pub type Signature<M> = ManagedByteArray<M, ED25519_SIGNATURE_BYTE_LEN>;
+/// Either a 64 bytes ed25519 signature, or a DER encoded secp256k1 (ECDSA) signature
+pub type Signature<M> = ManagedBuffer<M>;
+
+pub const SECP256K1_COMPRESSED_KEY_LEN: usize = 33;
+pub const SECP256K1_UNCOMPRESSED_KEY_LEN: usize = 65;
+
+/// DER encoded secp256k1 signatures are usually 70 to 72 bytes long,
+/// but may be a few bytes shorter when r or s have leading zero bytes
+pub const MIN_DER_SIGNATURE_LEN: usize = 68;
+pub const MAX_DER_SIGNATURE_LEN: usize = 72;
 
 pub static ALREADY_CLAIMED_ERR_MSG: &[u8] = b"Already claimed rewards for this week";
 pub static INVALID_WEEK_NR_ERR_MSG: &[u8] = b"Invalid week number";
 
 #[multiversx_sc::module]
 pub trait ValidationModule: crate::common_storage::CommonStorageModule {
-    fn verify_signature(&self, caller: &ManagedAddress, claim_arg: &ClaimArgsWrapper<Self::Api>) {
+    fn verify_signature(
+        &self,
+        caller: &ManagedAddress,
+        opt_recipient: Option<&ManagedAddress>,
+        claim_arg: &ClaimArgsWrapper<Self::Api>,
+    ) {
+        let data = self.build_signed_data(
+            caller,
+            opt_recipient,
+            claim_arg.week,
+            &claim_arg.user_delegation_amount,
+            &claim_arg.user_lkmex_staked_amount,
+            claim_arg.opt_user_energy.as_ref(),
+        );
+
+        let signature_len = claim_arg.signature.len();
+        if signature_len == ED25519_SIGNATURE_BYTE_LEN {
+            let signer = self.signer().get();
+            self.crypto()
+                .verify_ed25519(signer.as_managed_buffer(), &data, &claim_arg.signature);
+
+            return;
+        }
+
+        require!(
+            (MIN_DER_SIGNATURE_LEN..=MAX_DER_SIGNATURE_LEN).contains(&signature_len),
+            "Invalid signature length"
+        );
+
+        let secp256k1_signer_mapper = self.secp256k1_signer();
+        require!(
+            !secp256k1_signer_mapper.is_empty(),
+            "No secp256k1 signer set"
+        );
+
+        let signer = secp256k1_signer_mapper.get();
+        let is_valid = self.crypto().verify_custom_secp256k1(
+            &signer,
+            &data,
+            &claim_arg.signature,
+            MessageHashType::ECDSASha256,
+        );
+        require!(is_valid, "Invalid signature");
+    }
+
+    /// The signed message: week, user address, delegation amount, LKMEX staked amount,
+    /// then the user energy and the recipient, if any
+    fn build_signed_data(
+        &self,
+        caller: &ManagedAddress,
+        opt_recipient: Option<&ManagedAddress>,
+        week: Week,
+        user_delegation_amount: &BigUint,
+        user_lkmex_staked_amount: &BigUint,
+        opt_user_energy: Option<&BigUint>,
+    ) -> ManagedBuffer {
         let mut data = ManagedBuffer::new();
-        let _ = claim_arg.week.dep_encode(&mut data);
+        let _ = week.dep_encode(&mut data);
         data.append(caller.as_managed_buffer());
-        let _ = claim_arg.user_delegation_amount.dep_encode(&mut data);
-        let _ = claim_arg.user_lkmex_staked_amount.dep_encode(&mut data);
+        let _ = user_delegation_amount.dep_encode(&mut data);
+        let _ = user_lkmex_staked_amount.dep_encode(&mut data);
+        if let Some(user_energy) = opt_user_energy {
+            let _ = user_energy.dep_encode(&mut data);
+        }
+        if let Some(recipient) = opt_recipient {
+            data.append(recipient.as_managed_buffer());
+        }
 
-        let signer = self.signer().get();
-        self.crypto().verify_ed25519(
-            signer.as_managed_buffer(),
-            &data,
-            claim_arg.signature.as_managed_buffer(),
+        data
+    }
+
+    fn require_valid_secp256k1_key(&self, key: &ManagedBuffer) {
+        let key_len = key.len();
+        require!(
+            key_len == SECP256K1_COMPRESSED_KEY_LEN || key_len == SECP256K1_UNCOMPRESSED_KEY_LEN,
+            "Invalid secp256k1 key"
         );
     }
 
     fn validate_claim_args(
         &self,
         caller: &ManagedAddress,
+        opt_recipient: Option<&ManagedAddress>,
         claim_args: &ClaimArgArray<Self::Api>,
         claim_progress: &ShiftingClaimProgress<Self::Api>,
         last_checkpoint_week: Week,
@@ -39,7 +113,13 @@ pub trait ValidationModule: crate::common_storage::CommonStorageModule {
         self.check_no_duplicate_claim_args(claim_args);
 
         for claim_arg in claim_args {
-            self.validate_single_claim_arg(caller, claim_arg, claim_progress, last_checkpoint_week);
+            self.validate_single_claim_arg(
+                caller,
+                opt_recipient,
+                claim_arg,
+                claim_progress,
+                last_checkpoint_week,
+            );
         }
     }
 
@@ -59,6 +139,7 @@ pub trait ValidationModule: crate::common_storage::CommonStorageModule {
     fn validate_single_claim_arg(
         &self,
         caller: &ManagedAddress,
+        opt_recipient: Option<&ManagedAddress>,
         claim_arg: &ClaimArgsWrapper<Self::Api>,
         claim_progress: &ShiftingClaimProgress<Self::Api>,
         last_checkpoint_week: Week,
@@ -78,6 +159,6 @@ pub trait ValidationModule: crate::common_storage::CommonStorageModule {
             require!(!unclaimed_projects.is_empty(), ALREADY_CLAIMED_ERR_MSG);
         }
 
-        self.verify_signature(caller, claim_arg);
+        self.verify_signature(caller, opt_recipient, claim_arg);
     }
 }
@@ -0,0 +1,292 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
+
+use crate::{
+    access_control::Role,
+    project::{Project, ProjectId},
+    rewards::Week,
+};
+
+pub type VestingPositionAsMultiResult<M> =
+    MultiValue4<TokenIdentifier<M>, u64, BigUint<M>, BigUint<M>>;
+
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, Copy)]
+pub struct VestingSchedule {
+    pub cliff_weeks: Week,
+    pub duration_weeks: Week,
+}
+
+/// Rewards are locked until vesting_start_week, after which
+/// a duration_weeks-th part of them is unlocked each week
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct VestingPosition<M: ManagedTypeApi> {
+    pub reward_token: TokenIdentifier<M>,
+    pub reward_nonce: u64,
+    pub total_amount: BigUint<M>,
+    pub released_amount: BigUint<M>,
+    pub vesting_start_week: Week,
+    pub duration_weeks: Week,
+}
+
+impl<M: ManagedTypeApi> VestingPosition<M> {
+    pub fn new(project: &Project<M>, reward_nonce: u64, schedule: &VestingSchedule) -> Self {
+        Self {
+            reward_token: project.reward_token.clone(),
+            reward_nonce,
+            total_amount: BigUint::zero(),
+            released_amount: BigUint::zero(),
+            vesting_start_week: project.end_week + 1 + schedule.cliff_weeks,
+            duration_weeks: schedule.duration_weeks,
+        }
+    }
+
+    pub fn get_vested_amount(&self, current_week: Week) -> BigUint<M> {
+        if current_week < self.vesting_start_week {
+            return BigUint::zero();
+        }
+
+        let weeks_passed = current_week - self.vesting_start_week + 1;
+        if weeks_passed >= self.duration_weeks {
+            return self.total_amount.clone();
+        }
+
+        &self.total_amount * weeks_passed as u32 / self.duration_weeks as u32
+    }
+
+    #[inline]
+    pub fn get_releasable_amount(&self, current_week: Week) -> BigUint<M> {
+        self.get_vested_amount(current_week) - &self.released_amount
+    }
+
+    #[inline]
+    pub fn get_locked_amount(&self, current_week: Week) -> BigUint<M> {
+        &self.total_amount - &self.get_vested_amount(current_week)
+    }
+}
+
+#[multiversx_sc::module]
+pub trait VestingModule:
+    crate::project::ProjectModule
+    + crate::access_control::AccessControlModule
+    + crate::common_storage::CommonStorageModule
+{
+    /// Sets a vesting schedule for the given project's rewards. Claimed rewards are then locked
+    /// until cliff_weeks weeks have passed since the project's last rewards week,
+    /// and are unlocked linearly over duration_weeks weeks afterwards.
+    /// A duration_weeks of 0 removes the vesting schedule.
+    ///
+    /// May only be set before the project starts.
+    #[endpoint(setProjectVesting)]
+    fn set_project_vesting(
+        &self,
+        project_id: ProjectId<Self::Api>,
+        cliff_weeks: Week,
+        duration_weeks: Week,
+    ) {
+        self.require_caller_owner_or_role(Role::ProjectManager);
+
+        let project = self.get_project_or_panic(&project_id);
+        let current_week = self.get_current_week();
+        require!(current_week < project.start_week, "Project already started");
+
+        if duration_weeks == 0 {
+            self.project_vesting(&project_id).clear();
+            return;
+        }
+
+        let schedule = VestingSchedule {
+            cliff_weeks,
+            duration_weeks,
+        };
+        self.project_vesting(&project_id).set(schedule);
+    }
+
+    /// Sends the unlocked part of the user's vested rewards for the given projects
+    #[endpoint(releaseVestedRewards)]
+    fn release_vested_rewards(
+        &self,
+        project_ids: MultiValueEncoded<ProjectId<Self::Api>>,
+    ) -> PaymentsVec<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let current_week = self.get_current_week();
+
+        let mut payments = PaymentsVec::new();
+        for project_id in project_ids {
+            let mapper = self.vesting_position(&caller, &project_id);
+            if mapper.is_empty() {
+                continue;
+            }
+
+            let mut position = mapper.get();
+            let releasable_amount = position.get_releasable_amount(current_week);
+            if releasable_amount == 0 {
+                continue;
+            }
+
+            position.released_amount += &releasable_amount;
+            self.release_project_vesting_amount(&project_id, &releasable_amount);
+            let total_vesting_mapper =
+                self.total_vesting_amount(&position.reward_token, position.reward_nonce);
+            let remaining_total_vesting = total_vesting_mapper.get() - &releasable_amount;
+            if remaining_total_vesting == 0 {
+                total_vesting_mapper.clear();
+                let _ = self
+                    .vesting_token_ids()
+                    .swap_remove(&(position.reward_token.clone(), position.reward_nonce));
+            } else {
+                total_vesting_mapper.set(&remaining_total_vesting);
+            }
+
+            if position.released_amount == position.total_amount {
+                mapper.clear();
+            } else {
+                mapper.set(&position);
+            }
+
+            payments.push(EsdtTokenPayment::new(
+                position.reward_token,
+                position.reward_nonce,
+                releasable_amount,
+            ));
+        }
+
+        if !payments.is_empty() {
+            self.send().direct_multi(&caller, &payments);
+        }
+
+        payments
+    }
+
+    /// Returns the user's vesting position for the given project. The results are, in order:
+    /// - reward_token
+    /// - reward_nonce
+    /// - vested_amount - unlocked, but not yet released
+    /// - locked_amount
+    #[view(getVestingPosition)]
+    fn get_vesting_position(
+        &self,
+        user: ManagedAddress,
+        project_id: ProjectId<Self::Api>,
+    ) -> OptionalValue<VestingPositionAsMultiResult<Self::Api>> {
+        let mapper = self.vesting_position(&user, &project_id);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        let position = mapper.get();
+        let current_week = self.get_current_week();
+        let vested_amount = position.get_releasable_amount(current_week);
+        let locked_amount = position.get_locked_amount(current_week);
+
+        OptionalValue::Some(
+            (
+                position.reward_token,
+                position.reward_nonce,
+                vested_amount,
+                locked_amount,
+            )
+                .into(),
+        )
+    }
+
+    fn add_to_vesting_position(
+        &self,
+        user: &ManagedAddress,
+        project_id: &ProjectId<Self::Api>,
+        project: &Project<Self::Api>,
+        schedule: &VestingSchedule,
+        amount: &BigUint,
+    ) {
+        let reward_nonce = self.project_reward_nonce(project_id).get();
+        let mapper = self.vesting_position(user, project_id);
+        let mut position = if !mapper.is_empty() {
+            let existing_position = mapper.get();
+            require!(
+                existing_position.reward_token == project.reward_token
+                    && existing_position.reward_nonce == reward_nonce,
+                "Vesting position token mismatch"
+            );
+
+            existing_position
+        } else {
+            VestingPosition::new(project, reward_nonce, schedule)
+        };
+        position.total_amount += amount;
+        mapper.set(&position);
+
+        self.project_vesting_amount(project_id)
+            .update(|total| *total += amount);
+
+        self.total_vesting_amount(&project.reward_token, reward_nonce)
+            .update(|total| *total += amount);
+        let _ = self
+            .vesting_token_ids()
+            .insert((project.reward_token.clone(), reward_nonce));
+    }
+
+    #[view(getProjectVesting)]
+    fn get_project_vesting(
+        &self,
+        project_id: ProjectId<Self::Api>,
+    ) -> OptionalValue<VestingSchedule> {
+        let mapper = self.project_vesting(&project_id);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    /// The ID stays reserved until all the vesting positions of the project are released,
+    /// so a new project with the same ID doesn't use the old positions
+    fn clear_project_vesting(&self, project_id: &ProjectId<Self::Api>) {
+        self.project_vesting(project_id).clear();
+        if !self.project_vesting_amount(project_id).is_empty() {
+            let _ = self.reserved_project_ids().insert(project_id.clone());
+        }
+    }
+
+    fn release_project_vesting_amount(&self, project_id: &ProjectId<Self::Api>, amount: &BigUint) {
+        let mapper = self.project_vesting_amount(project_id);
+        let remaining_amount = mapper.get() - amount;
+        if remaining_amount > 0 {
+            mapper.set(&remaining_amount);
+            return;
+        }
+
+        mapper.clear();
+        let _ = self.reserved_project_ids().swap_remove(project_id);
+    }
+
+    #[storage_mapper("projectVesting")]
+    fn project_vesting(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<VestingSchedule>;
+
+    /// Amount still held in the vesting positions of the project, over all users
+    #[storage_mapper("projectVestingAmount")]
+    fn project_vesting_amount(
+        &self,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("vestingPosition")]
+    fn vesting_position(
+        &self,
+        user: &ManagedAddress,
+        project_id: &ProjectId<Self::Api>,
+    ) -> SingleValueMapper<VestingPosition<Self::Api>>;
+
+    #[storage_mapper("totalVestingAmount")]
+    fn total_vesting_amount(
+        &self,
+        token_id: &TokenIdentifier,
+        token_nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("vestingTokenIds")]
+    fn vesting_token_ids(&self) -> UnorderedSetMapper<(TokenIdentifier, u64)>;
+}
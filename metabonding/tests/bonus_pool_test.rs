@@ -0,0 +1,91 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc_scenario::rust_biguint;
+
+static BONUS_TOKEN: &[u8] = b"BONUS-123456";
+
+#[test]
+fn bonus_pool_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    let owner_addr = mb_setup.owner_addr.clone();
+    mb_setup
+        .b_mock
+        .set_esdt_balance(&owner_addr, BONUS_TOKEN, &rust_biguint!(1_500));
+
+    // week 0 - only future weeks may be funded
+    mb_setup
+        .call_fund_bonus_pool(0, BONUS_TOKEN, 500, 0)
+        .assert_user_error("Invalid week");
+    mb_setup
+        .call_fund_bonus_pool(1, BONUS_TOKEN, 500, 101)
+        .assert_user_error("Invalid percentage");
+    mb_setup
+        .call_fund_bonus_pool(1, BONUS_TOKEN, 1_000, 20)
+        .assert_ok();
+    mb_setup
+        .call_fund_bonus_pool(1, BONUS_TOKEN, 500, 0)
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_bonus_pools_for_week(1),
+        vec![(BONUS_TOKEN.to_vec(), 1_500, 200, 1_500)]
+    );
+
+    mb_setup.set_current_epoch(20);
+    mb_setup
+        .call_add_rewards_checkpoint(1, 100_000, 200_000)
+        .assert_ok();
+    mb_setup.call_unpause().assert_ok();
+
+    // user has 25% of the week's delegation supply and 25% of the LKMEX staked amount,
+    // so gets 1,300 * 25% + 200 * 25% of the bonus on top of the rewards
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("99d5cda668ae530012ff24702c781a37476b1a9fb50c68f6710a0967711195a3a7e5e2eb006c3b726036682f1252a15b166b3109bbc591da9594d96cd4207703");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 50_000, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, BONUS_TOKEN, &rust_biguint!(375));
+    assert_eq!(
+        mb_setup.get_bonus_pools_for_week(1),
+        vec![(BONUS_TOKEN.to_vec(), 1_500, 200, 1_125)]
+    );
+
+    // the bonus is recorded with an empty project ID
+    assert_eq!(
+        mb_setup.get_claim_history(&first_user_addr, 0, 10),
+        vec![
+            (1, FIRST_PROJ_ID.to_vec(), 83_333_333),
+            (1, Vec::new(), 375)
+        ]
+    );
+
+    // undistributed bonus is owed until withdrawn
+    assert!(mb_setup
+        .get_solvency_report()
+        .contains(&(BONUS_TOKEN.to_vec(), 0, 1_125, 1_125)));
+    mb_setup
+        .call_withdraw_bonus_pool(1)
+        .assert_user_error("Bonus pool still claimable");
+
+    // week 11 - week 1 may no longer be claimed
+    mb_setup.b_mock.set_block_epoch(82);
+    mb_setup.call_withdraw_bonus_pool(1).assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&owner_addr, BONUS_TOKEN, &rust_biguint!(1_125));
+    assert!(mb_setup.get_bonus_pools_for_week(1).is_empty());
+    mb_setup
+        .call_withdraw_bonus_pool(1)
+        .assert_user_error("No bonus to withdraw");
+}
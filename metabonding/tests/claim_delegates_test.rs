@@ -0,0 +1,114 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc_scenario::rust_biguint;
+
+#[test]
+fn delegate_claim_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let delegate_addr = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+
+    mb_setup
+        .call_add_claim_delegate(&first_user_addr, &delegate_addr)
+        .assert_ok();
+    mb_setup
+        .call_add_claim_delegate(&first_user_addr, &delegate_addr)
+        .assert_user_error("Address is already a delegate");
+
+    // delegate claims on behalf of the user, rewards go to the user
+    mb_setup
+        .call_claim_rewards_for(
+            &delegate_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+        )
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&delegate_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+
+    // user revokes the delegate, then claims by themselves
+    mb_setup
+        .call_remove_claim_delegate(&first_user_addr, &delegate_addr)
+        .assert_ok();
+    mb_setup
+        .call_remove_claim_delegate(&first_user_addr, &delegate_addr)
+        .assert_user_error("Address is not a delegate");
+
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 2, 25_000, 0, &sig_first_user_week_2)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333 + 41_666_666),
+    );
+}
+
+#[test]
+fn delegate_claim_to_other_recipient_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let delegate_addr = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let sig_first_user_to_self_week_1 = hex_literal::hex!("0d2b5a1b8875c212b1c10ad74006a4f2dfede283d62f58a9ab8dd80d809ed6f7fe17f7a8be31f7c893e5b7c5f45f0df91d915071e29ad29ca7793c7528b67e05");
+
+    mb_setup
+        .call_add_claim_delegate(&first_user_addr, &delegate_addr)
+        .assert_ok();
+
+    // delegate can't redirect the user's rewards to themselves
+    mb_setup
+        .call_claim_rewards_to_for(
+            &delegate_addr,
+            &first_user_addr,
+            &delegate_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_to_self_week_1,
+        )
+        .assert_user_error("Invalid rewards recipient");
+
+    // user as recipient is allowed
+    mb_setup
+        .call_claim_rewards_to_for(
+            &delegate_addr,
+            &first_user_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_to_self_week_1,
+        )
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&delegate_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+}
@@ -59,6 +59,10 @@ fn claim_partial_ok_test() {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
                 ]
                 .into(),
                 2,
@@ -106,6 +110,10 @@ fn claim_partial_ok_test() {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
                 ]
                 .into(),
                 2,
@@ -153,6 +161,10 @@ fn claim_partial_ok_test() {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
                 ]
                 .into(),
                 2,
@@ -206,6 +218,10 @@ fn claim_partial_ok_test() {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
                 ]
                 .into(),
                 2,
@@ -256,6 +272,10 @@ fn claim_partial_ok_test() {
                     not_claimed.clone(),
                     not_claimed.clone(),
                     not_claimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
                 ]
                 .into(),
                 2,
@@ -264,3 +284,109 @@ fn claim_partial_ok_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn claim_skips_inactive_and_removed_projects_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+
+    // second project is not active in week 1, so it's marked as claimed without being loaded
+    mb_setup
+        .call_claim_partial_rewards(
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+            &[SECOND_PROJ_ID],
+        )
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, SECOND_PROJ_TOKEN, &rust_biguint!(0));
+
+    // claim only the first project for week 2
+    mb_setup
+        .call_claim_partial_rewards(
+            &first_user_addr,
+            2,
+            25_000,
+            0,
+            &sig_first_user_week_2,
+            &[FIRST_PROJ_ID],
+        )
+        .assert_ok();
+
+    mb_setup
+        .b_mock
+        .execute_query(&mb_setup.mb_wrapper, |sc| {
+            let shifting_progress = sc.claim_progress(&managed_address!(&first_user_addr)).get();
+            let expected_shifting_progress = ShiftingClaimProgress::new(
+                [
+                    ClaimFlag::Claimed {
+                        unclaimed_projects: ManagedVec::from_iter(vec![managed_buffer!(
+                            FIRST_PROJ_ID
+                        )]),
+                    },
+                    ClaimFlag::Claimed {
+                        unclaimed_projects: ManagedVec::from_iter(vec![managed_buffer!(
+                            SECOND_PROJ_ID
+                        )]),
+                    },
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                    ClaimFlag::NotClaimed,
+                ]
+                .into(),
+                2,
+            );
+            assert_eq!(shifting_progress, expected_shifting_progress);
+        })
+        .assert_ok();
+
+    // remove the second project
+    mb_setup.call_remove_project(SECOND_PROJ_ID).assert_ok();
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+
+    // the removed project is dropped from the week, without giving any rewards
+    mb_setup
+        .call_claim_partial_rewards(
+            &first_user_addr,
+            2,
+            25_000,
+            0,
+            &sig_first_user_week_2,
+            &[SECOND_PROJ_ID],
+        )
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, SECOND_PROJ_TOKEN, &rust_biguint!(0));
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(41_666_666),
+    );
+    mb_setup
+        .call_claim_partial_rewards(
+            &first_user_addr,
+            2,
+            25_000,
+            0,
+            &sig_first_user_week_2,
+            &[SECOND_PROJ_ID],
+        )
+        .assert_user_error("Already claimed rewards for this week");
+}
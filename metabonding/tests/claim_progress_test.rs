@@ -5,7 +5,7 @@ use multiversx_sc::types::{ManagedVec, MultiValueEncoded};
 use multiversx_sc_scenario::{managed_address, rust_biguint};
 
 use metabonding::{
-    claim_progress::{ClaimFlag, ClaimProgressModule, ShiftingClaimProgress},
+    claim_progress::{ClaimFlag, ClaimProgressModule, ShiftingClaimProgress, CLAIM_FLAGS_LEN},
     legacy_storage_cleanup::LegacyStorageCleanupModule,
 };
 use metabonding_setup::*;
@@ -43,15 +43,19 @@ fn claim_progress_migration_test() {
                         not_claimed.clone(),
                         not_claimed.clone(),
                         claimed.clone(),
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
                     ]
                     .into(),
                     5,
                 );
                 assert_eq!(shifting_progress, expected_shifting_progress);
 
-                // check shifted by 1
+                // check shifted by 1, once the window moves
                 let shifting_progress_after_1 =
-                    sc.get_claim_progress(&managed_address!(&first_user), 6);
+                    sc.get_claim_progress(&managed_address!(&first_user), CLAIM_FLAGS_LEN + 1);
                 let expected_shifting_progress_after_1 = ShiftingClaimProgress::new(
                     [
                         claimed.clone(),
@@ -59,9 +63,13 @@ fn claim_progress_migration_test() {
                         not_claimed.clone(),
                         claimed,
                         not_claimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
                     ]
                     .into(),
-                    6,
+                    CLAIM_FLAGS_LEN + 1,
                 );
                 assert_eq!(
                     shifting_progress_after_1,
@@ -71,7 +79,8 @@ fn claim_progress_migration_test() {
                 // check shifted when getting from storage
                 sc.claim_progress(&managed_address!(&first_user))
                     .set(&shifting_progress);
-                let shifted_from_storage = sc.get_claim_progress(&managed_address!(&first_user), 6);
+                let shifted_from_storage =
+                    sc.get_claim_progress(&managed_address!(&first_user), CLAIM_FLAGS_LEN + 1);
                 assert_eq!(shifted_from_storage, expected_shifting_progress_after_1);
             },
         )
@@ -118,6 +127,10 @@ fn claim_progress_cleanup_test() {
                         not_claimed.clone(),
                         not_claimed,
                         claimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
+                        ClaimFlag::NotClaimed,
                     ]
                     .into(),
                     5,
@@ -0,0 +1,223 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding::{
+    claim_progress::{ClaimFlag, ClaimProgressModule, ClaimProgressTracker},
+    lkmex_weighting::LkmexWeighting,
+};
+use metabonding_setup::*;
+use multiversx_sc::types::ManagedVec;
+use multiversx_sc_scenario::{managed_address, managed_buffer, rust_biguint};
+
+#[test]
+fn energy_weighted_lkmex_rewards_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.call_unpause().assert_ok();
+
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &mb_setup.first_project_owner.clone(),
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            1,
+            3,
+            25,
+        )
+        .assert_ok();
+    mb_setup
+        .call_set_project_lkmex_weighting(FIRST_PROJ_ID, LkmexWeighting::Energy)
+        .assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &mb_setup.first_project_owner.clone(),
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+        )
+        .assert_ok();
+
+    mb_setup.b_mock.set_block_epoch(15);
+
+    mb_setup
+        .call_set_project_lkmex_weighting(FIRST_PROJ_ID, LkmexWeighting::StakedAmount)
+        .assert_user_error("Project already started");
+    mb_setup
+        .call_add_rewards_checkpoint_with_energy(1, 100_000, 200_000, 1_000_000)
+        .assert_ok();
+
+    // without energy, only the delegation part is given
+    // 1/4 * 750,000,000 / 3 = 62,500,000
+    let rewards_without_energy = mb_setup.get_pretty_rewards(1, 25_000, 100_000)[0].2;
+    assert_eq!(rewards_without_energy, 62_500_000);
+
+    // (1/4 * 750,000,000 + 1/4 * 250,000,000) / 3 ~= 83,333,333
+    let expected_rewards_amount = 83_333_333u64;
+    let actual_rewards_amount =
+        mb_setup.get_pretty_rewards_with_energy(1, 25_000, 100_000, Some(250_000))[0].2;
+    assert_eq!(expected_rewards_amount, actual_rewards_amount);
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("8dc0733ee1709b3cc716b22bbd9dced95c8307eb2b2894c28d08c298a827bf380870142b5ecbfb85e32debfde54fb15c99946d51f5de76fb99e6be40ca6e4e0b");
+
+    // energy is part of the signed data
+    mb_setup
+        .call_claim_rewards_with_energy(
+            &first_user_addr,
+            1,
+            25_000,
+            100_000,
+            500_000,
+            &sig_first_user_week_1,
+        )
+        .assert_error(10, "invalid signature");
+
+    mb_setup
+        .call_claim_rewards_with_energy(
+            &first_user_addr,
+            1,
+            25_000,
+            100_000,
+            250_000,
+            &sig_first_user_week_1,
+        )
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(expected_rewards_amount),
+    );
+}
+
+#[test]
+fn energy_weighted_project_skipped_without_energy_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.call_unpause().assert_ok();
+
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    let second_project_owner = mb_setup.second_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            1,
+            3,
+            25,
+        )
+        .assert_ok();
+    mb_setup
+        .call_add_project(
+            SECOND_PROJ_ID,
+            &second_project_owner,
+            SECOND_PROJ_TOKEN,
+            TOTAL_SECOND_PROJ_TOKENS,
+            1,
+            3,
+            25,
+        )
+        .assert_ok();
+    mb_setup
+        .call_set_project_lkmex_weighting(FIRST_PROJ_ID, LkmexWeighting::Energy)
+        .assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+        )
+        .assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &second_project_owner,
+            SECOND_PROJ_ID,
+            SECOND_PROJ_TOKEN,
+            TOTAL_SECOND_PROJ_TOKENS,
+        )
+        .assert_ok();
+
+    // weeks with energy-weighted projects need the total energy
+    mb_setup.b_mock.set_block_epoch(15);
+    mb_setup
+        .call_add_rewards_checkpoint(1, 100_000, 200_000)
+        .assert_user_error("Missing total energy for week");
+    mb_setup
+        .call_add_rewards_checkpoints(&[(1, 100_000, 200_000, None)])
+        .assert_user_error("Missing total energy for week");
+    mb_setup
+        .call_add_rewards_checkpoints(&[(1, 100_000, 200_000, Some(0))])
+        .assert_user_error("Invalid total energy");
+    mb_setup
+        .call_add_rewards_checkpoints(&[(1, 100_000, 200_000, Some(1_000_000))])
+        .assert_ok();
+
+    // without energy, the energy-weighted project is skipped
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_partial_rewards(
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+            &[FIRST_PROJ_ID],
+        )
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+
+    // 1/4 * 1,500,000,000 / 3 = 125,000,000
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        SECOND_PROJ_TOKEN,
+        &rust_biguint!(125_000_000),
+    );
+
+    // the energy-weighted project is still claimable for the week
+    mb_setup
+        .b_mock
+        .execute_query(&mb_setup.mb_wrapper, |sc| {
+            let claim_progress = sc.get_claim_progress(&managed_address!(&first_user_addr), 1);
+            assert_eq!(
+                claim_progress.get_claim_flags_for_week(1),
+                &ClaimFlag::Claimed {
+                    unclaimed_projects: ManagedVec::from_single_item(managed_buffer!(
+                        FIRST_PROJ_ID
+                    )),
+                }
+            );
+        })
+        .assert_ok();
+
+    let sig_with_energy_week_1 = hex_literal::hex!("8dc0733ee1709b3cc716b22bbd9dced95c8307eb2b2894c28d08c298a827bf380870142b5ecbfb85e32debfde54fb15c99946d51f5de76fb99e6be40ca6e4e0b");
+    mb_setup
+        .call_claim_rewards_with_energy(
+            &first_user_addr,
+            1,
+            25_000,
+            100_000,
+            250_000,
+            &sig_with_energy_week_1,
+        )
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        SECOND_PROJ_TOKEN,
+        &rust_biguint!(125_000_000),
+    );
+}
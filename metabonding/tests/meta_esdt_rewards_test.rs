@@ -0,0 +1,85 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc::codec::Empty;
+use multiversx_sc_scenario::rust_biguint;
+
+static META_PROJ_ID: &[u8] = b"MetaProj";
+static META_PROJ_TOKEN: &[u8] = b"META-123456";
+const META_PROJ_NONCE: u64 = 5;
+const TOTAL_META_PROJ_TOKENS: u64 = 300_000_000;
+
+#[test]
+fn meta_esdt_rewards_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            META_PROJ_ID,
+            &first_project_owner,
+            META_PROJ_TOKEN,
+            TOTAL_META_PROJ_TOKENS,
+            1,
+            3,
+            0,
+        )
+        .assert_ok();
+
+    mb_setup.b_mock.set_nft_balance(
+        &first_project_owner,
+        META_PROJ_TOKEN,
+        META_PROJ_NONCE,
+        &rust_biguint!(TOTAL_META_PROJ_TOKENS),
+        &Empty,
+    );
+    mb_setup
+        .call_deposit_rewards_with_nonce(
+            &first_project_owner,
+            META_PROJ_ID,
+            META_PROJ_TOKEN,
+            META_PROJ_NONCE,
+            TOTAL_META_PROJ_TOKENS,
+        )
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_project_reward_nonce(META_PROJ_ID),
+        META_PROJ_NONCE
+    );
+
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    // user has 25% of the 100_000_000 weekly rewards, paid with the deposited nonce
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    assert_eq!(
+        mb_setup
+            .b_mock
+            .get_esdt_balance(&first_user_addr, META_PROJ_TOKEN, META_PROJ_NONCE),
+        rust_biguint!(25_000_000)
+    );
+    assert_eq!(
+        mb_setup.get_solvency_report(),
+        vec![(
+            META_PROJ_TOKEN.to_vec(),
+            META_PROJ_NONCE,
+            275_000_000,
+            275_000_000
+        )]
+    );
+
+    // week 11 - project expired, leftover funds are refunded with the same nonce
+    mb_setup.b_mock.set_block_epoch(82);
+    mb_setup.call_clear_expired_projects().assert_ok();
+    assert_eq!(
+        mb_setup
+            .b_mock
+            .get_esdt_balance(&first_project_owner, META_PROJ_TOKEN, META_PROJ_NONCE),
+        rust_biguint!(275_000_000)
+    );
+    assert_eq!(mb_setup.get_project_reward_nonce(META_PROJ_ID), 0);
+}
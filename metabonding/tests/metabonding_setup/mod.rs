@@ -1,6 +1,24 @@
 #![allow(deprecated)]
 
+use metabonding::access_control::{AccessControlModule, Role};
+use metabonding::bonus_pool::BonusPoolModule;
+use metabonding::claim_delegates::ClaimDelegatesModule;
+use metabonding::claim_history::ClaimHistoryModule;
+use metabonding::lkmex_weighting::{LkmexWeighting, LkmexWeightingModule};
+use metabonding::migration::MigrationModule;
+use metabonding::pause::PauseModule;
+use metabonding::project_clearing::ProjectClearingModule;
+use metabonding::project_edit::ProjectEditModule;
+use metabonding::project_opt_out::ProjectOptOutModule;
+use metabonding::protocol_fee::ProtocolFeeModule;
+use metabonding::proxy_settings::ProxySettingsModule;
 use metabonding::rewards::RewardsModule;
+use metabonding::rollover::RolloverModule;
+use metabonding::sc_whitelist::SCWhitelistModule;
+use metabonding::solvency::SolvencyModule;
+use metabonding::timelock::{ActionId, TimelockModule};
+use metabonding::validation::ValidationModule;
+use metabonding::vesting::VestingModule;
 use metabonding::*;
 use metabonding::{claim::ClaimModule, project::ProjectModule};
 use metabonding::{
@@ -13,7 +31,6 @@ use multiversx_sc::{
     codec::multi_types::OptionalValue,
     types::{Address, MultiValueEncoded},
 };
-use multiversx_sc_modules::pause::PauseModule;
 use multiversx_sc_scenario::testing_framework::{
     BlockchainStateWrapper, ContractObjWrapper, TxResult,
 };
@@ -31,6 +48,8 @@ pub static FIRST_PROJ_TOKEN: &[u8] = b"PROJ-123456";
 pub static SECOND_PROJ_TOKEN: &[u8] = b"COOL-123456";
 pub const TOTAL_FIRST_PROJ_TOKENS: u64 = 1_000_000_000;
 pub const TOTAL_SECOND_PROJ_TOKENS: u64 = 2_000_000_000;
+// Wednesday, 21.02.2024, 12:30 GMT
+pub const START_TIMESTAMP: u64 = 1_708_518_600;
 
 pub struct MetabondingSetup<MetabondingObjBuilder>
 where
@@ -51,6 +70,15 @@ where
     MetabondingObjBuilder: 'static + Copy + Fn() -> metabonding::ContractObj<DebugApi>,
 {
     pub fn new(builder: MetabondingObjBuilder) -> Self {
+        Self::new_with_week_length(builder, None)
+    }
+
+    /// If a week length is given, the contract is deployed in timestamp mode,
+    /// with the current block timestamp set to START_TIMESTAMP
+    pub fn new_with_week_length(
+        builder: MetabondingObjBuilder,
+        opt_week_length_seconds: Option<u64>,
+    ) -> Self {
         let rust_zero = rust_biguint!(0);
         let mut b_mock = BlockchainStateWrapper::new();
         let owner_addr = b_mock.create_user_account(&rust_zero);
@@ -83,6 +111,7 @@ where
 
         let current_epoch = 5;
         b_mock.set_block_epoch(current_epoch);
+        b_mock.set_block_timestamp(START_TIMESTAMP);
 
         let mb_wrapper = b_mock.create_sc_account(
             &rust_zero,
@@ -93,9 +122,24 @@ where
         b_mock
             .execute_tx(&owner_addr, &mb_wrapper, &rust_zero, |sc| {
                 let signer_addr = managed_address!(&Address::from(&SIGNER_ADDRESS));
-                sc.init(signer_addr.clone(), OptionalValue::None);
-
-                assert_eq!(sc.first_week_start_epoch().get(), 5);
+                match opt_week_length_seconds {
+                    Some(week_length_seconds) => {
+                        sc.init(
+                            signer_addr.clone(),
+                            OptionalValue::None,
+                            OptionalValue::Some(week_length_seconds),
+                        );
+                        assert_eq!(sc.week_length_seconds().get(), week_length_seconds);
+                    }
+                    None => {
+                        sc.init(
+                            signer_addr.clone(),
+                            OptionalValue::None,
+                            OptionalValue::None,
+                        );
+                        assert_eq!(sc.first_week_start_epoch().get(), 5);
+                    }
+                }
                 assert_eq!(sc.signer().get(), signer_addr);
                 assert!(sc.is_paused());
             })
@@ -176,6 +220,10 @@ where
         self.b_mock.set_block_epoch(epoch);
     }
 
+    pub fn set_current_timestamp(&mut self, timestamp: u64) {
+        self.b_mock.set_block_timestamp(timestamp);
+    }
+
     pub fn advance_one_week(&mut self) {
         self.current_epoch += EPOCHS_IN_WEEK;
         self.b_mock.set_block_epoch(self.current_epoch);
@@ -193,12 +241,53 @@ where
     }
 
     pub fn call_unpause(&mut self) -> TxResult {
+        let owner_addr = self.owner_addr.clone();
+        self.call_unpause_as(&owner_addr)
+    }
+
+    pub fn call_unpause_as(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.unpause_endpoint();
+            })
+    }
+
+    pub fn call_pause_as(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.pause_endpoint();
+            })
+    }
+
+    pub fn is_paused(&mut self) -> bool {
+        let mut paused = false;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                paused = sc.is_paused();
+            })
+            .assert_ok();
+
+        paused
+    }
+
+    pub fn call_grant_role(&mut self, role: Role, address: &Address) -> TxResult {
         self.b_mock.execute_tx(
             &self.owner_addr,
             &self.mb_wrapper,
             &rust_biguint!(0),
             |sc| {
-                sc.unpause_endpoint();
+                sc.grant_role(role, managed_address!(address));
+            },
+        )
+    }
+
+    pub fn call_revoke_role(&mut self, role: Role, address: &Address) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.revoke_role(role, managed_address!(address));
             },
         )
     }
@@ -213,6 +302,30 @@ where
         start_week: Week,
         duration_weeks: Week,
         lkmex_rewards_percentage: u64,
+    ) -> TxResult {
+        self.call_add_project_with_deadline(
+            project_id,
+            project_owner,
+            reward_token,
+            reward_supply,
+            start_week,
+            duration_weeks,
+            lkmex_rewards_percentage,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn call_add_project_with_deadline(
+        &mut self,
+        project_id: &[u8],
+        project_owner: &Address,
+        reward_token: &[u8],
+        reward_supply: u64,
+        start_week: Week,
+        duration_weeks: Week,
+        lkmex_rewards_percentage: u64,
+        opt_deposit_deadline_week: Option<Week>,
     ) -> TxResult {
         self.b_mock.execute_tx(
             &self.owner_addr,
@@ -227,103 +340,232 @@ where
                     start_week,
                     duration_weeks,
                     lkmex_rewards_percentage,
+                    opt_deposit_deadline_week.into(),
                 );
             },
         )
     }
 
+    pub fn get_projects_awaiting_funding(&mut self) -> Vec<(Vec<u8>, Week)> {
+        let mut projects = Vec::new();
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_projects_awaiting_funding() {
+                    let (project_id, deposit_deadline_week) = entry.into_tuple();
+                    projects.push((
+                        project_id.to_boxed_bytes().as_slice().to_vec(),
+                        deposit_deadline_week,
+                    ));
+                }
+            })
+            .assert_ok();
+
+        projects
+    }
+
     pub fn call_remove_project(&mut self, project_id: &[u8]) -> TxResult {
         self.b_mock.execute_tx(
             &self.owner_addr,
             &self.mb_wrapper,
             &rust_biguint!(0),
             |sc| {
-                sc.remove_project(managed_buffer!(project_id));
+                let _ = sc.remove_project(managed_buffer!(project_id));
             },
         )
     }
 
-    pub fn call_clear_expired_projects(&mut self) -> TxResult {
+    pub fn call_change_secp256k1_signer(&mut self, new_signer: &[u8]) -> TxResult {
         self.b_mock.execute_tx(
             &self.owner_addr,
             &self.mb_wrapper,
             &rust_biguint!(0),
             |sc| {
-                let _ = sc.clear_expired_projects();
+                let _ = sc.change_secp256k1_signer(managed_buffer!(new_signer));
             },
         )
     }
 
-    pub fn get_all_project_ids(&mut self) -> Vec<Vec<u8>> {
-        let mut all_ids = Vec::new();
+    pub fn get_secp256k1_signer(&mut self) -> Vec<u8> {
+        let mut signer = Vec::new();
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                signer = sc.secp256k1_signer().get().to_boxed_bytes().into_vec();
+            })
+            .assert_ok();
+
+        signer
+    }
 
+    pub fn get_signed_data(
+        &mut self,
+        caller: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
         self.b_mock
             .execute_query(&self.mb_wrapper, |sc| {
-                let result = sc.get_all_project_ids_view();
+                data = sc
+                    .build_signed_data(
+                        &managed_address!(caller),
+                        None,
+                        week,
+                        &managed_biguint!(user_delegation_supply),
+                        &managed_biguint!(user_lkmex_staked),
+                        None,
+                    )
+                    .to_boxed_bytes()
+                    .into_vec();
+            })
+            .assert_ok();
 
-                for id in &result.to_vec() {
-                    all_ids.push(id.to_boxed_bytes().as_slice().to_vec());
-                }
+        data
+    }
+
+    pub fn call_execute_timelock_action(&mut self, action_id: ActionId) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.execute_timelock_action(action_id);
+            },
+        )
+    }
+
+    pub fn call_cancel_timelock_action(&mut self, action_id: ActionId) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.cancel_timelock_action(action_id);
+            },
+        )
+    }
+
+    pub fn call_set_timelock_delay(&mut self, delay_epochs: u64) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.set_timelock_delay(delay_epochs);
+            },
+        )
+    }
+
+    pub fn get_timelock_delay(&mut self) -> u64 {
+        let mut delay_epochs = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                delay_epochs = sc.timelock_delay_epochs().get();
             })
             .assert_ok();
 
-        all_ids
+        delay_epochs
     }
 
-    pub fn get_project_by_id(&mut self, proj_id: &[u8]) -> (Vec<u8>, u64, u64, Week, Week) {
-        let mut token = Vec::new();
-        let mut reward_amount = 0;
-        let mut lkmex_rewards_supply = 0;
-        let mut start_week = 0;
-        let mut duration = 0;
+    pub fn get_queued_action_ids(&mut self) -> Vec<ActionId> {
+        let mut action_ids = Vec::new();
 
         self.b_mock
             .execute_query(&self.mb_wrapper, |sc| {
-                let result = sc.get_project_by_id(managed_buffer!(proj_id));
-                let (first, second, third, fourth, fifth) = result.into_tuple();
-                token = first.to_boxed_bytes().as_slice().to_vec();
-                reward_amount = second.to_u64().unwrap();
-                lkmex_rewards_supply = third.to_u64().unwrap();
-                start_week = fourth;
-                duration = fifth;
+                for queued_action in sc.get_queued_actions() {
+                    let (action_id, _, _) = queued_action.into_tuple();
+                    action_ids.push(action_id);
+                }
             })
             .assert_ok();
 
-        (
-            token,
-            reward_amount,
-            lkmex_rewards_supply,
-            start_week,
-            duration,
+        action_ids
+    }
+
+    pub fn call_set_project_expiration_weeks(
+        &mut self,
+        project_id: &[u8],
+        expiration_weeks: Week,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_project_expiration_weeks(managed_buffer!(project_id), expiration_weeks);
+            },
         )
     }
 
-    pub fn call_add_rewards_checkpoint(
+    pub fn call_clear_expired_projects(&mut self) -> TxResult {
+        let owner_addr = self.owner_addr.clone();
+        self.call_clear_expired_projects_as(&owner_addr)
+    }
+
+    pub fn call_clear_expired_projects_as(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.clear_expired_projects();
+            })
+    }
+
+    pub fn get_clear_cursor_week(&mut self) -> Week {
+        let mut week = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                week = sc.clear_cursor_week().get();
+            })
+            .assert_ok();
+
+        week
+    }
+
+    pub fn get_all_project_ids(&mut self) -> Vec<Vec<u8>> {
+        let mut all_ids = Vec::new();
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                let result = sc.get_all_project_ids_view();
+
+                for id in &result.to_vec() {
+                    all_ids.push(id.to_boxed_bytes().as_slice().to_vec());
+                }
+            })
+            .assert_ok();
+
+        all_ids
+    }
+
+    pub fn call_edit_project(
         &mut self,
-        week: Week,
-        total_delegation_supply: u64,
-        total_lkmex_staked: u64,
+        project_id: &[u8],
+        start_week: Week,
+        duration_weeks: Week,
+        lkmex_rewards_percentage: u64,
     ) -> TxResult {
         self.b_mock.execute_tx(
             &self.owner_addr,
             &self.mb_wrapper,
             &rust_biguint!(0),
             |sc| {
-                sc.add_rewards_checkpoint(
-                    week,
-                    managed_biguint!(total_delegation_supply),
-                    managed_biguint!(total_lkmex_staked),
+                sc.edit_project(
+                    managed_buffer!(project_id),
+                    start_week,
+                    duration_weeks,
+                    lkmex_rewards_percentage,
                 );
             },
         )
     }
 
-    pub fn call_deposit_rewards(
+    pub fn call_extend_project(
         &mut self,
         caller: &Address,
         project_id: &[u8],
         token_id: &[u8],
         amount: u64,
+        extra_weeks: Week,
     ) -> TxResult {
         self.b_mock.execute_esdt_transfer(
             caller,
@@ -332,77 +574,856 @@ where
             0,
             &rust_biguint!(amount),
             |sc| {
-                sc.deposit_rewards(managed_buffer!(project_id));
+                sc.extend_project(managed_buffer!(project_id), extra_weeks);
             },
         )
     }
 
-    pub fn call_claim_rewards(
+    pub fn call_set_growth_program_target(
         &mut self,
         caller: &Address,
-        week: Week,
-        user_delegation_supply: u64,
-        user_lkmex_staked: u64,
-        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
+        project_id: &[u8],
+        sc_address: &Address,
+        growth_project_id: u32,
+        end_week: Week,
     ) -> TxResult {
         self.b_mock
             .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
-                let mut args = MultiValueEncoded::new();
-                args.push(
-                    (
-                        week,
-                        managed_biguint!(user_delegation_supply),
-                        managed_biguint!(user_lkmex_staked),
-                        signature.into(),
-                    )
-                        .into(),
+                sc.set_growth_program_target(
+                    managed_buffer!(project_id),
+                    managed_address!(sc_address),
+                    growth_project_id,
+                    end_week,
                 );
-
-                let _ = sc.claim_rewards(managed_address!(caller), args);
             })
     }
 
-    pub fn call_claim_partial_rewards(
+    pub fn call_remove_growth_program_target(
         &mut self,
         caller: &Address,
-        week: Week,
-        user_delegation_supply: u64,
-        user_lkmex_staked: u64,
-        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
-        projects_to_claim: &[&[u8]],
+        project_id: &[u8],
     ) -> TxResult {
         self.b_mock
             .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
-                let mut args = MultiValueEncoded::new();
-                args.push(
-                    (
-                        week,
-                        managed_biguint!(user_delegation_supply),
-                        managed_biguint!(user_lkmex_staked),
-                        signature.into(),
-                    )
-                        .into(),
-                );
+                sc.remove_growth_program_target(managed_buffer!(project_id));
+            })
+    }
 
-                let mut ptc = ManagedVec::new();
-                for proj_id in projects_to_claim {
-                    ptc.push(managed_buffer!(*proj_id));
+    pub fn get_growth_program_target(&mut self, project_id: &[u8]) -> Option<(Address, u32, Week)> {
+        let mut result = None;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                if let OptionalValue::Some(target) =
+                    sc.get_growth_program_target(managed_buffer!(project_id))
+                {
+                    result = Some((
+                        target.sc_address.to_address(),
+                        target.project_id,
+                        target.end_week,
+                    ));
                 }
+            })
+            .assert_ok();
 
-                let _ = sc.claim_partial_rewards(managed_address!(caller), ptc, args);
+        result
+    }
+
+    pub fn call_deposit_to_growth_program(
+        &mut self,
+        caller: &Address,
+        project_id: &[u8],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.deposit_to_growth_program(managed_buffer!(project_id));
             })
     }
 
-    pub fn call_claim_rewards_multiple(
+    pub fn call_refund_growth_program_deposit(
         &mut self,
         caller: &Address,
-        args: &[(Week, u64, u64, &[u8; ED25519_SIGNATURE_BYTE_LEN])],
+        project_id: &[u8],
     ) -> TxResult {
         self.b_mock
             .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
-                let mut encoded_args = MultiValueEncoded::new();
-                for arg in args {
-                    let (week, user_delegation_supply, user_lkmex_staked, signature) = *arg;
+                sc.refund_growth_program_deposit(managed_buffer!(project_id));
+            })
+    }
+
+    pub fn get_pending_growth_program_deposit(
+        &mut self,
+        project_id: &[u8],
+    ) -> Option<(Address, Vec<u8>, u64)> {
+        let mut result = None;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                if let OptionalValue::Some(pending_deposit) =
+                    sc.get_pending_growth_program_deposit(managed_buffer!(project_id))
+                {
+                    result = Some((
+                        pending_deposit.project_owner.to_address(),
+                        pending_deposit
+                            .token_id
+                            .to_boxed_bytes()
+                            .as_slice()
+                            .to_vec(),
+                        pending_deposit.amount.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn get_project_by_id(&mut self, proj_id: &[u8]) -> (Vec<u8>, u64, u64, Week, Week) {
+        let mut token = Vec::new();
+        let mut reward_amount = 0;
+        let mut lkmex_rewards_supply = 0;
+        let mut start_week = 0;
+        let mut duration = 0;
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                let result = sc.get_project_by_id(managed_buffer!(proj_id));
+                let (first, second, third, fourth, fifth, _) = result.into_tuple();
+                token = first.to_boxed_bytes().as_slice().to_vec();
+                reward_amount = second.to_u64().unwrap();
+                lkmex_rewards_supply = third.to_u64().unwrap();
+                start_week = fourth;
+                duration = fifth;
+            })
+            .assert_ok();
+
+        (
+            token,
+            reward_amount,
+            lkmex_rewards_supply,
+            start_week,
+            duration,
+        )
+    }
+
+    pub fn call_set_project_metadata(
+        &mut self,
+        caller: &Address,
+        project_id: &[u8],
+        name: &[u8],
+        description_hash: &[u8; 32],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.set_project_metadata(
+                    managed_buffer!(project_id),
+                    managed_buffer!(name),
+                    managed_buffer!(b"https://example.com"),
+                    description_hash.into(),
+                    managed_buffer!(b"DeFi"),
+                );
+            })
+    }
+
+    pub fn get_project_metadata_name(&mut self, project_id: &[u8]) -> Option<Vec<u8>> {
+        let mut opt_name = None;
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                let result = sc.get_project_by_id(managed_buffer!(project_id));
+                let (_, _, _, _, _, opt_metadata) = result.into_tuple();
+                if let OptionalValue::Some(metadata) = opt_metadata {
+                    opt_name = Some(metadata.name.to_boxed_bytes().as_slice().to_vec());
+                }
+            })
+            .assert_ok();
+
+        opt_name
+    }
+
+    pub fn call_set_project_rollover(&mut self, project_id: &[u8], enabled: bool) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_project_rollover(managed_buffer!(project_id), enabled);
+            },
+        )
+    }
+
+    pub fn call_set_max_protocol_fee_percentage(&mut self, max_fee_percentage: u64) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_max_protocol_fee_percentage(max_fee_percentage);
+            },
+        )
+    }
+
+    pub fn call_set_project_protocol_fee(
+        &mut self,
+        project_id: &[u8],
+        fee_percentage: u64,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_project_protocol_fee(managed_buffer!(project_id), fee_percentage);
+            },
+        )
+    }
+
+    pub fn call_set_treasury_address(&mut self, treasury_address: &Address) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_treasury_address(managed_address!(treasury_address));
+            },
+        )
+    }
+
+    pub fn call_withdraw_protocol_fees(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.withdraw_protocol_fees();
+            })
+    }
+
+    pub fn get_treasury_balances(&mut self) -> Vec<(Vec<u8>, u64, u64)> {
+        let mut balances = Vec::new();
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_treasury_balances() {
+                    let (token_id, token_nonce, balance) = entry.into_tuple();
+                    balances.push((
+                        token_id.to_boxed_bytes().as_slice().to_vec(),
+                        token_nonce,
+                        balance.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        balances
+    }
+
+    pub fn call_fund_bonus_pool(
+        &mut self,
+        week: Week,
+        token_id: &[u8],
+        amount: u64,
+        lkmex_rewards_percentage: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            token_id,
+            0,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.fund_bonus_pool(week, lkmex_rewards_percentage);
+            },
+        )
+    }
+
+    pub fn call_withdraw_bonus_pool(&mut self, week: Week) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.withdraw_bonus_pool(week);
+            },
+        )
+    }
+
+    pub fn get_bonus_pools_for_week(&mut self, week: Week) -> Vec<(Vec<u8>, u64, u64, u64)> {
+        let mut pools = Vec::new();
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_bonus_pools_for_week(week) {
+                    let (token_id, total_amount, lkmex_amount, remaining_amount) =
+                        entry.into_tuple();
+                    pools.push((
+                        token_id.to_boxed_bytes().as_slice().to_vec(),
+                        total_amount.to_u64().unwrap(),
+                        lkmex_amount.to_u64().unwrap(),
+                        remaining_amount.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        pools
+    }
+
+    pub fn get_rolled_over_amount(&mut self, project_id: &[u8], week: Week) -> u64 {
+        let mut amount = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                amount = sc
+                    .get_rolled_over_amount(managed_buffer!(project_id), week)
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        amount
+    }
+
+    pub fn call_upgrade(&mut self) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.upgrade();
+            },
+        )
+    }
+
+    pub fn get_storage_version(&mut self) -> u32 {
+        let mut version = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                version = sc.storage_version().get();
+            })
+            .assert_ok();
+
+        version
+    }
+
+    pub fn get_claim_history(
+        &mut self,
+        user: &Address,
+        from: usize,
+        size: usize,
+    ) -> Vec<(Week, Vec<u8>, u64)> {
+        let mut history = Vec::new();
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_claim_history(managed_address!(user), from, size) {
+                    history.push((
+                        entry.week,
+                        entry.project_id.to_boxed_bytes().as_slice().to_vec(),
+                        entry.amount.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        history
+    }
+
+    pub fn call_set_project_vesting(
+        &mut self,
+        project_id: &[u8],
+        cliff_weeks: Week,
+        duration_weeks: Week,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_project_vesting(managed_buffer!(project_id), cliff_weeks, duration_weeks);
+            },
+        )
+    }
+
+    pub fn call_release_vested_rewards(
+        &mut self,
+        caller: &Address,
+        project_ids: &[&[u8]],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for project_id in project_ids {
+                    args.push(managed_buffer!(*project_id));
+                }
+
+                let _ = sc.release_vested_rewards(args);
+            })
+    }
+
+    /// Returns the vested (releasable) and locked amounts
+    pub fn get_vesting_position(&mut self, user: &Address, project_id: &[u8]) -> (u64, u64) {
+        let mut amounts = (0, 0);
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                let result = sc
+                    .get_vesting_position(managed_address!(user), managed_buffer!(project_id))
+                    .into_option();
+                if let Some(position) = result {
+                    let (_, _, vested_amount, locked_amount) = position.into_tuple();
+                    amounts = (
+                        vested_amount.to_u64().unwrap(),
+                        locked_amount.to_u64().unwrap(),
+                    );
+                }
+            })
+            .assert_ok();
+
+        amounts
+    }
+
+    pub fn call_withdraw_surplus(&mut self, token_id: &[u8]) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.withdraw_surplus(managed_token_id!(token_id), OptionalValue::None);
+            },
+        )
+    }
+
+    pub fn get_solvency_report(&mut self) -> Vec<(Vec<u8>, u64, u64, u64)> {
+        let mut report = Vec::new();
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_solvency_report() {
+                    let (token, token_nonce, owed_amount, sc_balance) = entry.into_tuple();
+                    report.push((
+                        token.to_boxed_bytes().as_slice().to_vec(),
+                        token_nonce,
+                        owed_amount.to_u64().unwrap(),
+                        sc_balance.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        report
+    }
+
+    pub fn call_add_rewards_checkpoint(
+        &mut self,
+        week: Week,
+        total_delegation_supply: u64,
+        total_lkmex_staked: u64,
+    ) -> TxResult {
+        let owner = self.owner_addr.clone();
+        self.call_add_rewards_checkpoint_as(
+            &owner,
+            week,
+            total_delegation_supply,
+            total_lkmex_staked,
+        )
+    }
+
+    pub fn call_add_rewards_checkpoint_as(
+        &mut self,
+        caller: &Address,
+        week: Week,
+        total_delegation_supply: u64,
+        total_lkmex_staked: u64,
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.add_rewards_checkpoint(
+                    week,
+                    managed_biguint!(total_delegation_supply),
+                    managed_biguint!(total_lkmex_staked),
+                    OptionalValue::None,
+                );
+            })
+    }
+
+    pub fn call_add_rewards_checkpoint_with_energy(
+        &mut self,
+        week: Week,
+        total_delegation_supply: u64,
+        total_lkmex_staked: u64,
+        total_energy: u64,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_rewards_checkpoint(
+                    week,
+                    managed_biguint!(total_delegation_supply),
+                    managed_biguint!(total_lkmex_staked),
+                    OptionalValue::Some(managed_biguint!(total_energy)),
+                );
+            },
+        )
+    }
+
+    pub fn call_add_rewards_checkpoints(
+        &mut self,
+        entries: &[(Week, u64, u64, Option<u64>)],
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut checkpoints = MultiValueEncoded::new();
+                for (week, total_delegation_supply, total_lkmex_staked, total_energy) in entries {
+                    checkpoints.push(
+                        (
+                            *week,
+                            managed_biguint!(*total_delegation_supply),
+                            managed_biguint!(*total_lkmex_staked),
+                            total_energy.map(|total_energy| managed_biguint!(total_energy)),
+                        )
+                            .into(),
+                    );
+                }
+
+                sc.add_rewards_checkpoints(checkpoints);
+            },
+        )
+    }
+
+    pub fn get_last_checkpoint_week(&mut self) -> Week {
+        let mut week = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                week = sc.get_last_checkpoint_week();
+            })
+            .assert_ok();
+
+        week
+    }
+
+    pub fn call_set_project_lkmex_weighting(
+        &mut self,
+        project_id: &[u8],
+        weighting: LkmexWeighting,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_project_lkmex_weighting(managed_buffer!(project_id), weighting);
+            },
+        )
+    }
+
+    pub fn call_claim_rewards_with_energy(
+        &mut self,
+        caller: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        user_energy: u64,
+        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        week,
+                        managed_biguint!(user_delegation_supply),
+                        managed_biguint!(user_lkmex_staked),
+                        managed_biguint!(user_energy),
+                        signature.into(),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.claim_rewards_with_energy(managed_address!(caller), args);
+            })
+    }
+
+    pub fn call_deposit_rewards(
+        &mut self,
+        caller: &Address,
+        project_id: &[u8],
+        token_id: &[u8],
+        amount: u64,
+    ) -> TxResult {
+        self.call_deposit_rewards_with_nonce(caller, project_id, token_id, 0, amount)
+    }
+
+    pub fn call_deposit_rewards_with_nonce(
+        &mut self,
+        caller: &Address,
+        project_id: &[u8],
+        token_id: &[u8],
+        token_nonce: u64,
+        amount: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            caller,
+            &self.mb_wrapper,
+            token_id,
+            token_nonce,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.deposit_rewards(managed_buffer!(project_id));
+            },
+        )
+    }
+
+    pub fn get_project_reward_nonce(&mut self, project_id: &[u8]) -> u64 {
+        let mut nonce = 0;
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                nonce = sc.get_project_reward_nonce(managed_buffer!(project_id));
+            })
+            .assert_ok();
+
+        nonce
+    }
+
+    pub fn call_claim_rewards(
+        &mut self,
+        caller: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        signature: &[u8],
+    ) -> TxResult {
+        self.call_claim_rewards_for(
+            caller,
+            caller,
+            week,
+            user_delegation_supply,
+            user_lkmex_staked,
+            signature,
+        )
+    }
+
+    pub fn call_claim_rewards_for(
+        &mut self,
+        caller: &Address,
+        original_caller: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        signature: &[u8],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        week,
+                        managed_biguint!(user_delegation_supply),
+                        managed_biguint!(user_lkmex_staked),
+                        signature.into(),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.claim_rewards(managed_address!(original_caller), args);
+            })
+    }
+
+    pub fn call_claim_rewards_to(
+        &mut self,
+        caller: &Address,
+        recipient: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
+    ) -> TxResult {
+        self.call_claim_rewards_to_for(
+            caller,
+            caller,
+            recipient,
+            week,
+            user_delegation_supply,
+            user_lkmex_staked,
+            signature,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn call_claim_rewards_to_for(
+        &mut self,
+        caller: &Address,
+        original_caller: &Address,
+        recipient: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        week,
+                        managed_biguint!(user_delegation_supply),
+                        managed_biguint!(user_lkmex_staked),
+                        signature.into(),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.claim_rewards_to(
+                    managed_address!(original_caller),
+                    managed_address!(recipient),
+                    args,
+                );
+            })
+    }
+
+    pub fn call_add_claim_delegate(&mut self, user: &Address, delegate: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(user, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.add_claim_delegate(managed_address!(delegate));
+            })
+    }
+
+    pub fn call_remove_claim_delegate(&mut self, user: &Address, delegate: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(user, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                sc.remove_claim_delegate(managed_address!(delegate));
+            })
+    }
+
+    pub fn whitelist_proxy(&mut self, proxy: &Address) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_addr,
+                &self.mb_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.sc_whitelist_addresses().add(&managed_address!(proxy));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn call_set_proxy_settings(&mut self, proxy: &Address, enabled: bool) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_proxy_settings(managed_address!(proxy), managed_buffer!(b"proxy"), enabled);
+            },
+        )
+    }
+
+    pub fn call_set_proxy_weekly_cap(
+        &mut self,
+        proxy: &Address,
+        token_id: &[u8],
+        weekly_cap: u64,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner_addr,
+            &self.mb_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_proxy_weekly_cap(
+                    managed_address!(proxy),
+                    managed_token_id!(token_id),
+                    managed_biguint!(weekly_cap),
+                );
+            },
+        )
+    }
+
+    pub fn get_proxy_claim_stats(
+        &mut self,
+        proxy: &Address,
+        week: Week,
+    ) -> Vec<(Vec<u8>, u64, u64)> {
+        let mut stats = Vec::new();
+
+        self.b_mock
+            .execute_query(&self.mb_wrapper, |sc| {
+                for entry in sc.get_proxy_claim_stats(managed_address!(proxy), week) {
+                    let (token, claimed_in_week, total_claimed) = entry.into_tuple();
+                    stats.push((
+                        token.to_boxed_bytes().as_slice().to_vec(),
+                        claimed_in_week.to_u64().unwrap(),
+                        total_claimed.to_u64().unwrap(),
+                    ));
+                }
+            })
+            .assert_ok();
+
+        stats
+    }
+
+    pub fn call_opt_out_of_projects(&mut self, user: &Address, project_ids: &[&[u8]]) -> TxResult {
+        self.b_mock
+            .execute_tx(user, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for project_id in project_ids {
+                    args.push(managed_buffer!(project_id));
+                }
+
+                sc.opt_out_of_projects(args);
+            })
+    }
+
+    pub fn call_opt_in_to_projects(&mut self, user: &Address, project_ids: &[&[u8]]) -> TxResult {
+        self.b_mock
+            .execute_tx(user, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for project_id in project_ids {
+                    args.push(managed_buffer!(project_id));
+                }
+
+                sc.opt_in_to_projects(args);
+            })
+    }
+
+    pub fn call_claim_partial_rewards(
+        &mut self,
+        caller: &Address,
+        week: Week,
+        user_delegation_supply: u64,
+        user_lkmex_staked: u64,
+        signature: &[u8; ED25519_SIGNATURE_BYTE_LEN],
+        projects_to_claim: &[&[u8]],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                args.push(
+                    (
+                        week,
+                        managed_biguint!(user_delegation_supply),
+                        managed_biguint!(user_lkmex_staked),
+                        signature.into(),
+                    )
+                        .into(),
+                );
+
+                let mut ptc = ManagedVec::new();
+                for proj_id in projects_to_claim {
+                    ptc.push(managed_buffer!(*proj_id));
+                }
+
+                let _ = sc.claim_partial_rewards(managed_address!(caller), ptc, args);
+            })
+    }
+
+    pub fn call_claim_rewards_multiple(
+        &mut self,
+        caller: &Address,
+        args: &[(Week, u64, u64, &[u8; ED25519_SIGNATURE_BYTE_LEN])],
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.mb_wrapper, &rust_biguint!(0), |sc| {
+                let mut encoded_args = MultiValueEncoded::new();
+                for arg in args {
+                    let (week, user_delegation_supply, user_lkmex_staked, signature) = *arg;
 
                     encoded_args.push(
                         (
@@ -441,6 +1462,21 @@ where
         week: Week,
         user_delegation_amount: u64,
         user_lkmex_staked_amount: u64,
+    ) -> Vec<(Vec<u8>, Vec<u8>, u64)> {
+        self.get_pretty_rewards_with_energy(
+            week,
+            user_delegation_amount,
+            user_lkmex_staked_amount,
+            None,
+        )
+    }
+
+    pub fn get_pretty_rewards_with_energy(
+        &mut self,
+        week: Week,
+        user_delegation_amount: u64,
+        user_lkmex_staked_amount: u64,
+        opt_user_energy: Option<u64>,
     ) -> Vec<(Vec<u8>, Vec<u8>, u64)> {
         let mut rewards = Vec::new();
 
@@ -450,6 +1486,9 @@ where
                     week,
                     managed_biguint!(user_delegation_amount),
                     managed_biguint!(user_lkmex_staked_amount),
+                    opt_user_energy
+                        .map(|user_energy| managed_biguint!(user_energy))
+                        .into(),
                 );
 
                 for rew in result {
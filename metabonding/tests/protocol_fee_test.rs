@@ -0,0 +1,68 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc_scenario::rust_biguint;
+
+#[test]
+fn protocol_fee_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.call_unpause().assert_ok();
+
+    mb_setup
+        .call_set_max_protocol_fee_percentage(101)
+        .assert_user_error("Invalid percentage");
+    mb_setup
+        .call_set_max_protocol_fee_percentage(10)
+        .assert_ok();
+    mb_setup
+        .call_set_project_protocol_fee(FIRST_PROJ_ID, 20)
+        .assert_user_error("Fee above maximum");
+    mb_setup
+        .call_set_project_protocol_fee(FIRST_PROJ_ID, 10)
+        .assert_ok();
+
+    // the fee can't be changed once the project started
+    mb_setup.add_default_checkpoints();
+    mb_setup
+        .call_set_project_protocol_fee(FIRST_PROJ_ID, 5)
+        .assert_user_error("Project already started");
+
+    let treasury_addr = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+    mb_setup
+        .call_set_treasury_address(&treasury_addr)
+        .assert_ok();
+
+    // 10% of the 83_333_333 weekly reward is kept as fee
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(75_000_000),
+    );
+    assert_eq!(
+        mb_setup.get_treasury_balances(),
+        vec![(FIRST_PROJ_TOKEN.to_vec(), 0, 8_333_333)]
+    );
+
+    // only the treasury may withdraw
+    mb_setup
+        .call_withdraw_protocol_fees(&first_user_addr)
+        .assert_user_error("Permission denied");
+    mb_setup
+        .call_withdraw_protocol_fees(&treasury_addr)
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&treasury_addr, FIRST_PROJ_TOKEN, &rust_biguint!(8_333_333));
+    assert!(mb_setup.get_treasury_balances().is_empty());
+    mb_setup
+        .call_withdraw_protocol_fees(&treasury_addr)
+        .assert_user_error("No fees to withdraw");
+}
@@ -0,0 +1,152 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc_scenario::rust_biguint;
+
+const ROLLOVER_PROJ_TOKENS: u64 = 800_000_000;
+
+#[test]
+fn rollover_unclaimed_rewards_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.call_unpause().assert_ok();
+
+    // 100,000,000 tokens per week, for weeks 1-8
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            ROLLOVER_PROJ_TOKENS,
+            1,
+            8,
+            0,
+        )
+        .assert_ok();
+    mb_setup
+        .call_set_project_rollover(FIRST_PROJ_ID, true)
+        .assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            ROLLOVER_PROJ_TOKENS,
+        )
+        .assert_ok();
+
+    // week 1 - first user claims a quarter of the week's rewards
+    mb_setup.set_current_epoch(12);
+    mb_setup
+        .call_add_rewards_checkpoint(1, 100_000, 0)
+        .assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(25_000_000),
+    );
+
+    // week 5 - week 1 may still be claimed
+    mb_setup.set_current_epoch(40);
+    for week in 2..=5 {
+        mb_setup
+            .call_add_rewards_checkpoint(week, 100_000, 0)
+            .assert_ok();
+    }
+    for week in 6..=8 {
+        assert_eq!(mb_setup.get_rolled_over_amount(FIRST_PROJ_ID, week), 0);
+    }
+
+    // week 6 - claim window for week 1 is closed
+    // unclaimed 75,000,000 are split between weeks 6, 7 and 8
+    mb_setup.set_current_epoch(47);
+    mb_setup
+        .call_add_rewards_checkpoint(6, 100_000, 0)
+        .assert_ok();
+    for week in 6..=8 {
+        assert_eq!(
+            mb_setup.get_rolled_over_amount(FIRST_PROJ_ID, week),
+            25_000_000
+        );
+    }
+
+    // (100,000,000 + 25,000,000) / 4 = 31,250,000
+    let rewards = mb_setup.get_pretty_rewards(6, 25_000, 0);
+    assert_eq!(rewards[0].2, 31_250_000);
+}
+
+#[test]
+fn rollover_excludes_opted_out_rewards_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.call_unpause().assert_ok();
+
+    // 100,000,000 tokens per week, for weeks 1-8
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            ROLLOVER_PROJ_TOKENS,
+            1,
+            8,
+            0,
+        )
+        .assert_ok();
+    mb_setup
+        .call_set_project_rollover(FIRST_PROJ_ID, true)
+        .assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            ROLLOVER_PROJ_TOKENS,
+        )
+        .assert_ok();
+
+    // week 1 - first user opted out, so their quarter is forgone
+    mb_setup.set_current_epoch(12);
+    mb_setup
+        .call_add_rewards_checkpoint(1, 100_000, 0)
+        .assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_opt_out_of_projects(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+
+    mb_setup.set_current_epoch(40);
+    for week in 2..=5 {
+        mb_setup
+            .call_add_rewards_checkpoint(week, 100_000, 0)
+            .assert_ok();
+    }
+
+    // week 6 - the forgone rewards are not rolled over,
+    // only the 75,000,000 not claimed by anyone are split between weeks 6, 7 and 8
+    mb_setup.set_current_epoch(47);
+    mb_setup
+        .call_add_rewards_checkpoint(6, 100_000, 0)
+        .assert_ok();
+    for week in 6..=8 {
+        assert_eq!(
+            mb_setup.get_rolled_over_amount(FIRST_PROJ_ID, week),
+            25_000_000
+        );
+    }
+}
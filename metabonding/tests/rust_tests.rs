@@ -1,14 +1,48 @@
 #![allow(deprecated)]
 pub mod metabonding_setup;
 
+use k256::ecdsa::{signature::Verifier, VerifyingKey};
+use metabonding::access_control::Role;
+use metabonding::lkmex_weighting::{LkmexWeighting, LkmexWeightingModule};
+use metabonding::migration::MigrationModule;
+use metabonding::project::ProjectModule;
+use metabonding::protocol_fee::ProtocolFeeModule;
+use metabonding::rollover::RolloverModule;
+use metabonding::timelock::TimelockModule;
+use metabonding::vesting::VestingModule;
 use metabonding_setup::*;
-use multiversx_sc_scenario::rust_biguint;
+use multiversx_sc::storage::mappers::StorageClearable;
+use multiversx_sc_scenario::{managed_buffer, rust_biguint};
 
 #[test]
 fn init_test() {
     let _ = MetabondingSetup::new(metabonding::contract_obj);
 }
 
+#[test]
+fn timestamp_weeks_test() {
+    let one_hour = 60 * 60;
+    let mut mb_setup =
+        MetabondingSetup::new_with_week_length(metabonding::contract_obj, Some(one_hour));
+
+    // start is aligned down to the full hour
+    let first_week_start = START_TIMESTAMP - START_TIMESTAMP % one_hour;
+    assert_eq!(mb_setup.get_current_week(), 0);
+
+    // epochs are ignored in timestamp mode
+    mb_setup.set_current_epoch(100);
+    assert_eq!(mb_setup.get_current_week(), 0);
+
+    mb_setup.set_current_timestamp(first_week_start + one_hour - 1);
+    assert_eq!(mb_setup.get_current_week(), 0);
+
+    mb_setup.set_current_timestamp(first_week_start + one_hour);
+    assert_eq!(mb_setup.get_current_week(), 1);
+
+    mb_setup.set_current_timestamp(first_week_start + 5 * one_hour + 10);
+    assert_eq!(mb_setup.get_current_week(), 5);
+}
+
 #[test]
 fn add_projects_test() {
     let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
@@ -111,6 +145,70 @@ fn add_rewards_checkpoints_test() {
         .assert_user_error("Invalid checkpoint week");
 }
 
+#[test]
+fn checkpoint_operator_role_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.set_current_epoch(20);
+
+    let operator = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    // try add checkpoint without the role
+    mb_setup
+        .call_add_rewards_checkpoint_as(&operator, 1, 100_000, 0)
+        .assert_user_error("Permission denied");
+
+    // project manager role is not enough
+    mb_setup
+        .call_grant_role(Role::ProjectManager, &operator)
+        .assert_ok();
+    mb_setup
+        .call_add_rewards_checkpoint_as(&operator, 1, 100_000, 0)
+        .assert_user_error("Permission denied");
+
+    mb_setup
+        .call_grant_role(Role::CheckpointOperator, &operator)
+        .assert_ok();
+    mb_setup
+        .call_add_rewards_checkpoint_as(&operator, 1, 100_000, 0)
+        .assert_ok();
+
+    mb_setup
+        .call_revoke_role(Role::CheckpointOperator, &operator)
+        .assert_ok();
+    mb_setup
+        .call_add_rewards_checkpoint_as(&operator, 2, 200_000, 0)
+        .assert_user_error("Permission denied");
+}
+
+#[test]
+fn pauser_role_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    let pauser = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+
+    mb_setup
+        .call_unpause_as(&pauser)
+        .assert_user_error("Permission denied");
+    assert!(mb_setup.is_paused());
+
+    mb_setup.call_grant_role(Role::Pauser, &pauser).assert_ok();
+    mb_setup.call_unpause_as(&pauser).assert_ok();
+    assert!(!mb_setup.is_paused());
+
+    mb_setup.call_pause_as(&pauser).assert_ok();
+    assert!(mb_setup.is_paused());
+
+    mb_setup.call_revoke_role(Role::Pauser, &pauser).assert_ok();
+    mb_setup
+        .call_unpause_as(&pauser)
+        .assert_user_error("Permission denied");
+
+    // the owner may always unpause
+    mb_setup.call_unpause().assert_ok();
+    assert!(!mb_setup.is_paused());
+}
+
 #[test]
 fn claim_rewards_test() {
     let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
@@ -430,12 +528,1050 @@ fn clear_project_funds_not_deposited_test() {
         vec![FIRST_PROJ_ID.to_vec(), SECOND_PROJ_ID.to_vec(),]
     );
 
-    // set current week - week 9 - first project expires
+    // set current week - week 9 - first project expires,
+    // second project is cancelled, as its deposit deadline passed
     mb_setup.b_mock.set_block_epoch(68);
 
     mb_setup.call_clear_expired_projects().assert_ok();
 
     // get project IDs after
     let proj_ids = mb_setup.get_all_project_ids();
-    assert_eq!(proj_ids, vec![SECOND_PROJ_ID.to_vec(),]);
+    assert!(proj_ids.is_empty());
+    assert!(mb_setup.get_projects_awaiting_funding().is_empty());
+}
+
+#[test]
+fn clear_expired_projects_queue_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    // week 8 - only the first project is expired. Anyone may clear.
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    mb_setup.b_mock.set_block_epoch(61);
+    mb_setup
+        .call_clear_expired_projects_as(&first_user_addr)
+        .assert_ok();
+    assert_eq!(mb_setup.get_clear_cursor_week(), 9);
+    assert_eq!(
+        mb_setup.get_all_project_ids(),
+        vec![SECOND_PROJ_ID.to_vec()]
+    );
+    mb_setup.b_mock.check_esdt_balance(
+        &mb_setup.first_project_owner.clone(),
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS),
+    );
+
+    // nothing new is due yet
+    mb_setup
+        .call_clear_expired_projects_as(&first_user_addr)
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_all_project_ids(),
+        vec![SECOND_PROJ_ID.to_vec()]
+    );
+
+    // week 11 - second project is expired
+    mb_setup.b_mock.set_block_epoch(82);
+    mb_setup
+        .call_clear_expired_projects_as(&first_user_addr)
+        .assert_ok();
+    assert_eq!(mb_setup.get_clear_cursor_week(), 12);
+    assert!(mb_setup.get_all_project_ids().is_empty());
+    mb_setup.b_mock.check_esdt_balance(
+        &mb_setup.second_project_owner.clone(),
+        SECOND_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_SECOND_PROJ_TOKENS),
+    );
+}
+
+#[test]
+fn add_rewards_checkpoints_batch_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+
+    // week 5
+    mb_setup.set_current_epoch(40);
+
+    // out of order entry rejects the whole batch
+    mb_setup
+        .call_add_rewards_checkpoints(&[
+            (1, 100_000, 0, None),
+            (2, 200_000, 0, None),
+            (4, 300_000, 0, None),
+        ])
+        .assert_user_error("Invalid checkpoint week");
+    assert_eq!(mb_setup.get_last_checkpoint_week(), 0);
+
+    mb_setup
+        .call_add_rewards_checkpoints(&[
+            (1, 100_000, 0, None),
+            (2, 200_000, 0, None),
+            (3, 300_000, 0, None),
+        ])
+        .assert_ok();
+    assert_eq!(mb_setup.get_last_checkpoint_week(), 3);
+
+    // future week
+    mb_setup
+        .call_add_rewards_checkpoints(&[
+            (4, 100_000, 0, None),
+            (5, 100_000, 0, None),
+            (6, 100_000, 0, None),
+        ])
+        .assert_user_error("Invalid checkpoint week");
+    assert_eq!(mb_setup.get_last_checkpoint_week(), 3);
+
+    mb_setup
+        .call_add_rewards_checkpoints(&[(4, 100_000, 0, None), (5, 100_000, 0, None)])
+        .assert_ok();
+    assert_eq!(mb_setup.get_last_checkpoint_week(), 5);
+}
+
+#[test]
+fn project_expiration_weeks_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+
+    mb_setup
+        .call_set_project_expiration_weeks(FIRST_PROJ_ID, 0)
+        .assert_user_error("Invalid expiration period");
+    mb_setup
+        .call_set_project_expiration_weeks(
+            FIRST_PROJ_ID,
+            metabonding::project::MAX_PROJECT_EXPIRATION_WEEKS + 1,
+        )
+        .assert_user_error("Invalid expiration period");
+    mb_setup
+        .call_set_project_expiration_weeks(FIRST_PROJ_ID, 8)
+        .assert_ok();
+
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+    mb_setup
+        .call_set_project_expiration_weeks(FIRST_PROJ_ID, 4)
+        .assert_user_error("Project already started");
+
+    // week 9 - week 1 would have been closed with the default expiration period
+    mb_setup.b_mock.set_block_epoch(68);
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    assert_eq!(
+        mb_setup.get_user_claimable_weeks(&first_user_addr),
+        &[1usize, 2usize]
+    );
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+
+    // week 11 - only the second project expired
+    mb_setup.b_mock.set_block_epoch(82);
+    mb_setup.call_clear_expired_projects().assert_ok();
+    assert_eq!(mb_setup.get_all_project_ids(), vec![FIRST_PROJ_ID.to_vec()]);
+
+    // week 12 - first project expired as well
+    mb_setup.b_mock.set_block_epoch(89);
+    mb_setup.call_clear_expired_projects().assert_ok();
+    assert!(mb_setup.get_all_project_ids().is_empty());
+    mb_setup.b_mock.check_esdt_balance(
+        &mb_setup.first_project_owner.clone(),
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS - 83_333_333),
+    );
+}
+
+#[test]
+fn claimable_weeks_default_expiration_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+
+    // week 5 - last week for claiming week 1 with the default expiration period
+    mb_setup.set_current_epoch(40);
+    assert_eq!(
+        mb_setup.get_user_claimable_weeks(&first_user_addr),
+        &[1usize, 2usize]
+    );
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    assert_eq!(
+        mb_setup.get_user_claimable_weeks(&first_user_addr),
+        &[2usize]
+    );
+
+    // week 6 - week 2 is still claimable, even though the claim progress keeps more weeks
+    mb_setup.advance_one_week();
+    assert_eq!(
+        mb_setup.get_user_claimable_weeks(&first_user_addr),
+        &[2usize]
+    );
+
+    // week 7 - week 2 expired for both projects
+    mb_setup.advance_one_week();
+    assert!(mb_setup
+        .get_user_claimable_weeks(&first_user_addr)
+        .is_empty());
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 2, 25_000, 0, &sig_first_user_week_2)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+}
+
+#[test]
+fn edit_and_extend_project_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+
+    mb_setup
+        .call_edit_project(FIRST_PROJ_ID, 2, 4, 101)
+        .assert_user_error("Invalid percentage");
+    mb_setup
+        .call_edit_project(FIRST_PROJ_ID, 2, 4, 50)
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_project_by_id(FIRST_PROJ_ID),
+        (FIRST_PROJ_TOKEN.to_vec(), 500_000_000, 500_000_000, 2, 5)
+    );
+    mb_setup
+        .call_set_project_vesting(SECOND_PROJ_ID, 0, 4)
+        .assert_ok();
+
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+
+    // week 2 - project started
+    mb_setup
+        .call_edit_project(FIRST_PROJ_ID, 3, 4, 50)
+        .assert_user_error("Project already started");
+
+    // no rewards in week 1 anymore
+    assert!(mb_setup.get_pretty_rewards(1, 25_000, 0).is_empty());
+
+    // extending by 2 weeks requires 2 more weekly rewards
+    let owner_addr = mb_setup.owner_addr.clone();
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .b_mock
+        .set_esdt_balance(&owner_addr, FIRST_PROJ_TOKEN, &rust_biguint!(1_000_000_000));
+    mb_setup.b_mock.set_esdt_balance(
+        &first_project_owner,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(500_000_000),
+    );
+    mb_setup
+        .call_extend_project(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            500_000_000,
+            2,
+        )
+        .assert_user_error("Permission denied");
+    mb_setup
+        .call_extend_project(&owner_addr, FIRST_PROJ_ID, FIRST_PROJ_TOKEN, 400_000_000, 2)
+        .assert_user_error("Invalid amount");
+    mb_setup
+        .call_extend_project(&owner_addr, FIRST_PROJ_ID, FIRST_PROJ_TOKEN, 500_000_000, 2)
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_project_by_id(FIRST_PROJ_ID),
+        (FIRST_PROJ_TOKEN.to_vec(), 750_000_000, 750_000_000, 2, 7)
+    );
+
+    // weekly rewards are unchanged
+    let mut rewards_week_2 = mb_setup.get_pretty_rewards(2, 25_000, 0);
+    rewards_week_2.sort();
+    assert_eq!(
+        rewards_week_2,
+        vec![
+            (
+                FIRST_PROJ_ID.to_vec(),
+                FIRST_PROJ_TOKEN.to_vec(),
+                15_625_000
+            ),
+            (
+                SECOND_PROJ_ID.to_vec(),
+                SECOND_PROJ_TOKEN.to_vec(),
+                50_000_000
+            ),
+        ]
+    );
+
+    // vesting of the second project starts after its last week
+    mb_setup
+        .b_mock
+        .set_esdt_balance(&owner_addr, SECOND_PROJ_TOKEN, &rust_biguint!(800_000_000));
+    mb_setup
+        .call_extend_project(
+            &owner_addr,
+            SECOND_PROJ_ID,
+            SECOND_PROJ_TOKEN,
+            800_000_000,
+            2,
+        )
+        .assert_user_error("Project has a vesting schedule");
+
+    // week 8 - first project ended
+    mb_setup.b_mock.set_block_epoch(61);
+    mb_setup
+        .call_extend_project(&owner_addr, FIRST_PROJ_ID, FIRST_PROJ_TOKEN, 500_000_000, 2)
+        .assert_user_error("Project already ended");
+}
+
+#[test]
+fn deposit_deadline_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.call_unpause().assert_ok();
+
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    let second_project_owner = mb_setup.second_project_owner.clone();
+    mb_setup
+        .call_add_project_with_deadline(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            1,
+            3,
+            0,
+            Some(4),
+        )
+        .assert_user_error("Invalid deposit deadline");
+    mb_setup
+        .call_add_project_with_deadline(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            1,
+            3,
+            0,
+            Some(1),
+        )
+        .assert_ok();
+    mb_setup
+        .call_add_project(
+            SECOND_PROJ_ID,
+            &second_project_owner,
+            SECOND_PROJ_TOKEN,
+            TOTAL_SECOND_PROJ_TOKENS,
+            2,
+            5,
+            0,
+        )
+        .assert_ok();
+
+    assert_eq!(
+        mb_setup.get_projects_awaiting_funding(),
+        vec![(FIRST_PROJ_ID.to_vec(), 1), (SECOND_PROJ_ID.to_vec(), 6)]
+    );
+
+    // set current week - week 2 - first project's deadline passed
+    mb_setup.b_mock.set_block_epoch(20);
+
+    mb_setup
+        .call_deposit_rewards(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+        )
+        .assert_user_error("Deposit deadline passed");
+    assert_eq!(
+        mb_setup.get_projects_awaiting_funding(),
+        vec![(SECOND_PROJ_ID.to_vec(), 6)]
+    );
+    assert_eq!(
+        mb_setup.get_all_project_ids(),
+        vec![SECOND_PROJ_ID.to_vec()]
+    );
+
+    mb_setup
+        .call_deposit_rewards(
+            &second_project_owner,
+            SECOND_PROJ_ID,
+            SECOND_PROJ_TOKEN,
+            TOTAL_SECOND_PROJ_TOKENS,
+        )
+        .assert_ok();
+    assert!(mb_setup.get_projects_awaiting_funding().is_empty());
+
+    // cancelled project is removed
+    mb_setup.call_clear_expired_projects().assert_ok();
+    mb_setup
+        .call_deposit_rewards(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+        )
+        .assert_user_error("Invalid project ID");
+}
+
+#[test]
+fn remove_project_timelock_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    mb_setup.call_remove_project(FIRST_PROJ_ID).assert_ok();
+    mb_setup.call_remove_project(SECOND_PROJ_ID).assert_ok();
+    assert_eq!(mb_setup.get_queued_action_ids(), vec![1, 2]);
+
+    // try execute before the delay has passed
+    mb_setup
+        .call_execute_timelock_action(1)
+        .assert_user_error("Action is still timelocked");
+
+    // cancel removal of the second project
+    mb_setup.call_cancel_timelock_action(2).assert_ok();
+    assert_eq!(mb_setup.get_queued_action_ids(), vec![1]);
+
+    mb_setup.advance_one_week();
+    mb_setup
+        .call_execute_timelock_action(2)
+        .assert_user_error("Invalid action ID");
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+    assert!(mb_setup.get_queued_action_ids().is_empty());
+
+    let proj_ids = mb_setup.get_all_project_ids();
+    assert_eq!(proj_ids, vec![SECOND_PROJ_ID.to_vec()]);
+    mb_setup.b_mock.check_esdt_balance(
+        &mb_setup.first_project_owner.clone(),
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS),
+    );
+
+    // action can only be executed once
+    mb_setup
+        .call_execute_timelock_action(1)
+        .assert_user_error("Invalid action ID");
+}
+
+#[test]
+fn removed_project_settings_cleared_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    mb_setup
+        .call_set_project_vesting(FIRST_PROJ_ID, 1, 4)
+        .assert_ok();
+    mb_setup
+        .call_set_max_protocol_fee_percentage(10)
+        .assert_ok();
+    mb_setup
+        .call_set_project_protocol_fee(FIRST_PROJ_ID, 5)
+        .assert_ok();
+    mb_setup
+        .call_set_project_lkmex_weighting(FIRST_PROJ_ID, LkmexWeighting::Energy)
+        .assert_ok();
+    mb_setup
+        .call_set_project_rollover(FIRST_PROJ_ID, true)
+        .assert_ok();
+
+    mb_setup.call_remove_project(FIRST_PROJ_ID).assert_ok();
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+
+    // re-add a project with the same ID
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            3,
+            3,
+            0,
+        )
+        .assert_ok();
+
+    // no settings are inherited from the removed project
+    mb_setup
+        .b_mock
+        .execute_query(&mb_setup.mb_wrapper, |sc| {
+            let project_id = managed_buffer!(FIRST_PROJ_ID);
+            assert!(sc.project_vesting(&project_id).is_empty());
+            assert!(sc.project_protocol_fee_percentage(&project_id).is_empty());
+            assert!(sc.project_lkmex_weighting(&project_id).is_empty());
+            assert!(!sc.rollover_projects().contains(&project_id));
+            assert!(!sc.rewards_deposited(&project_id).get());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn timelock_delay_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    let default_delay = metabonding::timelock::DEFAULT_TIMELOCK_DELAY_EPOCHS;
+
+    // increases take effect immediately
+    mb_setup
+        .call_set_timelock_delay(default_delay + 7)
+        .assert_ok();
+    assert_eq!(mb_setup.get_timelock_delay(), default_delay + 7);
+    assert!(mb_setup.get_queued_action_ids().is_empty());
+
+    // decreases are queued with the current delay
+    mb_setup.call_set_timelock_delay(1).assert_ok();
+    assert_eq!(mb_setup.get_timelock_delay(), default_delay + 7);
+    assert_eq!(mb_setup.get_queued_action_ids(), vec![1]);
+
+    mb_setup.advance_one_week();
+    mb_setup
+        .call_execute_timelock_action(1)
+        .assert_user_error("Action is still timelocked");
+
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+    assert_eq!(mb_setup.get_timelock_delay(), 1);
+}
+
+#[test]
+fn withdraw_surplus_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    // nothing to withdraw, the SC holds exactly what is owed
+    mb_setup
+        .call_withdraw_surplus(FIRST_PROJ_TOKEN)
+        .assert_user_error("No surplus to withdraw");
+
+    // tokens sent by mistake
+    let sc_address = mb_setup.mb_wrapper.address_ref().clone();
+    mb_setup.b_mock.set_esdt_balance(
+        &sc_address,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS + 1_000),
+    );
+
+    let report = mb_setup.get_solvency_report();
+    assert_eq!(
+        report,
+        vec![
+            (
+                FIRST_PROJ_TOKEN.to_vec(),
+                0,
+                TOTAL_FIRST_PROJ_TOKENS,
+                TOTAL_FIRST_PROJ_TOKENS + 1_000
+            ),
+            (
+                SECOND_PROJ_TOKEN.to_vec(),
+                0,
+                TOTAL_SECOND_PROJ_TOKENS,
+                TOTAL_SECOND_PROJ_TOKENS
+            ),
+        ]
+    );
+
+    mb_setup.call_withdraw_surplus(FIRST_PROJ_TOKEN).assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &mb_setup.owner_addr.clone(),
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(1_000),
+    );
+    mb_setup.b_mock.check_esdt_balance(
+        &sc_address,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS),
+    );
+}
+
+#[test]
+fn claim_to_recipient_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let second_user_addr = mb_setup.second_user_addr.clone();
+
+    // signature binds the second user as recipient
+    let sig_first_user_week_1_to_second_user = hex_literal::hex!("4321c14594cd994613644842b7d1d804842720f53c6fcfa3af1b010fab19e82318866406a1f74ac72b212dfc849c46fef06fe370cb0aa0b4a62737e6435f8e05");
+
+    // signature does not match another recipient
+    mb_setup
+        .call_claim_rewards_to(
+            &first_user_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1_to_second_user,
+        )
+        .assert_error(10, "invalid signature");
+
+    // legacy signature does not include a recipient
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    mb_setup
+        .call_claim_rewards_to(
+            &first_user_addr,
+            &second_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+        )
+        .assert_error(10, "invalid signature");
+
+    mb_setup
+        .call_claim_rewards_to(
+            &first_user_addr,
+            &second_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1_to_second_user,
+        )
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &second_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+}
+
+#[test]
+fn proxy_quota_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let proxy_addr = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+
+    mb_setup.whitelist_proxy(&proxy_addr);
+    mb_setup
+        .call_set_proxy_settings(&proxy_addr, false)
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards_for(
+            &proxy_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+        )
+        .assert_user_error("Proxy is disabled");
+
+    mb_setup
+        .call_set_proxy_settings(&proxy_addr, true)
+        .assert_ok();
+    mb_setup
+        .call_set_proxy_weekly_cap(&proxy_addr, FIRST_PROJ_TOKEN, 50_000_000)
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards_for(
+            &proxy_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+        )
+        .assert_user_error("Proxy weekly claim cap exceeded");
+
+    mb_setup
+        .call_set_proxy_weekly_cap(&proxy_addr, FIRST_PROJ_TOKEN, 100_000_000)
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards_for(
+            &proxy_addr,
+            &first_user_addr,
+            1,
+            25_000,
+            0,
+            &sig_first_user_week_1,
+        )
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&proxy_addr, FIRST_PROJ_TOKEN, &rust_biguint!(83_333_333));
+
+    let current_week = mb_setup.get_current_week();
+    let stats = mb_setup.get_proxy_claim_stats(&proxy_addr, current_week);
+    assert_eq!(
+        stats,
+        vec![(FIRST_PROJ_TOKEN.to_vec(), 83_333_333, 83_333_333)]
+    );
+}
+
+#[test]
+fn project_opt_out_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+
+    mb_setup
+        .call_opt_out_of_projects(&first_user_addr, &[b"InvalidProj"])
+        .assert_user_error("Invalid project ID");
+    mb_setup
+        .call_opt_out_of_projects(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+
+    // forgone rewards stay with the project
+    let report = mb_setup.get_solvency_report();
+    assert!(report.contains(&(
+        FIRST_PROJ_TOKEN.to_vec(),
+        0,
+        TOTAL_FIRST_PROJ_TOKENS,
+        TOTAL_FIRST_PROJ_TOKENS
+    )));
+
+    // week is settled for the project, even after opting back in
+    mb_setup
+        .call_opt_in_to_projects(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_user_error("Already claimed rewards for this week");
+}
+
+#[test]
+fn project_metadata_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    let second_project_owner = mb_setup.second_project_owner.clone();
+    let owner_addr = mb_setup.owner_addr.clone();
+    let description_hash = [1u8; 32];
+
+    assert_eq!(mb_setup.get_project_metadata_name(FIRST_PROJ_ID), None);
+
+    mb_setup
+        .call_set_project_metadata(
+            &second_project_owner,
+            FIRST_PROJ_ID,
+            b"First Project",
+            &description_hash,
+        )
+        .assert_user_error("Permission denied");
+
+    mb_setup
+        .call_set_project_metadata(
+            &first_project_owner,
+            FIRST_PROJ_ID,
+            b"First Project",
+            &description_hash,
+        )
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_project_metadata_name(FIRST_PROJ_ID),
+        Some(b"First Project".to_vec())
+    );
+
+    // SC owner may also update the metadata
+    mb_setup
+        .call_set_project_metadata(
+            &owner_addr,
+            FIRST_PROJ_ID,
+            b"Renamed Project",
+            &description_hash,
+        )
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_project_metadata_name(FIRST_PROJ_ID),
+        Some(b"Renamed Project".to_vec())
+    );
+    assert_eq!(mb_setup.get_project_metadata_name(SECOND_PROJ_ID), None);
+}
+
+#[test]
+fn upgrade_migration_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    assert_eq!(
+        mb_setup.get_storage_version(),
+        metabonding::migration::CURRENT_STORAGE_VERSION
+    );
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+
+    // simulate a contract deployed before versioning, the timelock and the active week index
+    let owner_addr = mb_setup.owner_addr.clone();
+    mb_setup
+        .b_mock
+        .execute_tx(&owner_addr, &mb_setup.mb_wrapper, &rust_biguint!(0), |sc| {
+            sc.storage_version().clear();
+            sc.timelock_delay_epochs().clear();
+            for week in 1..=5 {
+                sc.projects_active_in_week(week).clear();
+            }
+        })
+        .assert_ok();
+    assert_eq!(mb_setup.get_storage_version(), 0);
+    assert!(mb_setup.get_pretty_rewards(2, 25_000, 0).is_empty());
+
+    mb_setup.call_upgrade().assert_ok();
+    assert_eq!(
+        mb_setup.get_storage_version(),
+        metabonding::migration::CURRENT_STORAGE_VERSION
+    );
+    mb_setup
+        .b_mock
+        .execute_query(&mb_setup.mb_wrapper, |sc| {
+            assert_eq!(
+                sc.timelock_delay_epochs().get(),
+                metabonding::timelock::DEFAULT_TIMELOCK_DELAY_EPOCHS
+            );
+        })
+        .assert_ok();
+    assert_eq!(mb_setup.get_pretty_rewards(2, 25_000, 0).len(), 2);
+
+    // upgrading again does nothing
+    mb_setup.call_upgrade().assert_ok();
+}
+
+#[test]
+fn secp256k1_signer_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+
+    // signatures that are neither ed25519 nor DER encoded are rejected up front
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &[1u8; 40])
+        .assert_user_error("Invalid signature length");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &[1u8; 73])
+        .assert_user_error("Invalid signature length");
+
+    // non-ed25519 signatures require a secp256k1 signer
+    let der_signature = hex_literal::hex!("3045022100f74cb4897d5f0151bdf4d58b5fcb53ed2368881b08df8330fb724096fd0dceb002205cdf94eded278bf916159331f1190886120fe4b9e38f061edf5644cc0eac4d9e");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &der_signature)
+        .assert_user_error("No secp256k1 signer set");
+
+    mb_setup
+        .call_change_secp256k1_signer(&[2u8; 20])
+        .assert_user_error("Invalid secp256k1 key");
+
+    let compressed_key =
+        hex_literal::hex!("024b2b5e1b9be31c4f663d875b1d785bae82e63f4c9d3c203e2476b15d582ebbe1");
+    mb_setup
+        .call_change_secp256k1_signer(&compressed_key)
+        .assert_ok();
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+    assert_eq!(mb_setup.get_secp256k1_signer(), compressed_key);
+
+    // the DER signature of the signer is valid for the data built by the contract,
+    // using ECDSA over the SHA-256 hash, as the node checks it
+    let signed_data = mb_setup.get_signed_data(&first_user_addr, 1, 25_000, 0);
+    let verifying_key = VerifyingKey::from_sec1_bytes(&compressed_key).unwrap();
+    let signature = k256::ecdsa::Signature::from_der(&der_signature).unwrap();
+    assert!(verifying_key.verify(&signed_data, &signature).is_ok());
+    assert!(verifying_key.verify(&signed_data[1..], &signature).is_err());
+
+    // ed25519 signatures are still checked against the ed25519 signer
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(83_333_333),
+    );
+
+    // empty key removes the secp256k1 signer
+    mb_setup.call_change_secp256k1_signer(&[]).assert_ok();
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(2).assert_ok();
+    assert!(mb_setup.get_secp256k1_signer().is_empty());
+}
+
+#[test]
+fn claim_history_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_1 = hex_literal::hex!("d47c0d67b2d25de8b4a3f43d91a2b5ccb522afac47321ae80bf89c90a4445b26adefa693ab685fa20891f736d74eb2dedc11c4b1a8d6e642fa28df270d6ebe08");
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+
+    assert!(mb_setup
+        .get_claim_history(&first_user_addr, 0, 10)
+        .is_empty());
+
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 1, 25_000, 0, &sig_first_user_week_1)
+        .assert_ok();
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 2, 25_000, 0, &sig_first_user_week_2)
+        .assert_ok();
+
+    // newest entries first
+    let history = mb_setup.get_claim_history(&first_user_addr, 0, 10);
+    assert_eq!(
+        history,
+        vec![
+            (2, SECOND_PROJ_ID.to_vec(), 50_000_000),
+            (2, FIRST_PROJ_ID.to_vec(), 41_666_666),
+            (1, FIRST_PROJ_ID.to_vec(), 83_333_333),
+        ]
+    );
+
+    let page = mb_setup.get_claim_history(&first_user_addr, 1, 1);
+    assert_eq!(page, vec![(2, FIRST_PROJ_ID.to_vec(), 41_666_666)]);
+    assert!(mb_setup
+        .get_claim_history(&first_user_addr, 3, 10)
+        .is_empty());
+}
+
+#[test]
+fn growth_program_target_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    let owner_addr = mb_setup.owner_addr.clone();
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    let growth_program_address = mb_setup.mb_wrapper.address_ref().clone();
+
+    mb_setup
+        .call_set_growth_program_target(&owner_addr, FIRST_PROJ_ID, &first_project_owner, 1, 10)
+        .assert_user_error("Invalid growth-program address");
+
+    // rewards could only be deposited starting with the growth-program's next week
+    mb_setup
+        .call_set_growth_program_target(&owner_addr, FIRST_PROJ_ID, &growth_program_address, 1, 1)
+        .assert_user_error("Invalid end week");
+    mb_setup
+        .call_set_growth_program_target(&owner_addr, FIRST_PROJ_ID, &growth_program_address, 1, 10)
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_growth_program_target(FIRST_PROJ_ID),
+        Some((growth_program_address.clone(), 1, 10))
+    );
+
+    mb_setup
+        .call_set_growth_program_target(&owner_addr, SECOND_PROJ_ID, &growth_program_address, 2, 12)
+        .assert_ok();
+    mb_setup
+        .call_remove_growth_program_target(&owner_addr, SECOND_PROJ_ID)
+        .assert_ok();
+    assert_eq!(mb_setup.get_growth_program_target(SECOND_PROJ_ID), None);
+
+    // first project expires in week 8, its funds are kept for the growth-program
+    mb_setup.set_current_epoch(61);
+    mb_setup.call_clear_expired_projects().assert_ok();
+    assert_eq!(
+        mb_setup.get_all_project_ids(),
+        vec![SECOND_PROJ_ID.to_vec()]
+    );
+    assert_eq!(mb_setup.get_growth_program_target(FIRST_PROJ_ID), None);
+    assert_eq!(
+        mb_setup.get_pending_growth_program_deposit(FIRST_PROJ_ID),
+        Some((
+            first_project_owner.clone(),
+            FIRST_PROJ_TOKEN.to_vec(),
+            TOTAL_FIRST_PROJ_TOKENS
+        ))
+    );
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_project_owner, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+    assert!(mb_setup.get_solvency_report().contains(&(
+        FIRST_PROJ_TOKEN.to_vec(),
+        0,
+        TOTAL_FIRST_PROJ_TOKENS,
+        TOTAL_FIRST_PROJ_TOKENS
+    )));
+
+    // the ID can't be reused until the funds are sent
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            FIRST_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            9,
+            3,
+            0,
+        )
+        .assert_user_error("ID already in use");
+
+    // the growth-program rejects the deposit, which does not affect clearing
+    let deposit_result = mb_setup.call_deposit_to_growth_program(&owner_addr, FIRST_PROJ_ID);
+    assert_ne!(deposit_result.result_status, 0);
+    assert!(mb_setup
+        .get_pending_growth_program_deposit(FIRST_PROJ_ID)
+        .is_some());
+
+    // only the SC owner or the project owner may claim the refund
+    let rand_user = mb_setup.b_mock.create_user_account(&rust_biguint!(0));
+    mb_setup
+        .call_refund_growth_program_deposit(&rand_user, FIRST_PROJ_ID)
+        .assert_user_error("Permission denied");
+
+    mb_setup
+        .call_refund_growth_program_deposit(&first_project_owner, FIRST_PROJ_ID)
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_project_owner,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(TOTAL_FIRST_PROJ_TOKENS),
+    );
+    assert_eq!(
+        mb_setup.get_pending_growth_program_deposit(FIRST_PROJ_ID),
+        None
+    );
+    mb_setup
+        .call_refund_growth_program_deposit(&owner_addr, FIRST_PROJ_ID)
+        .assert_user_error("No pending deposit");
 }
@@ -0,0 +1,175 @@
+#![allow(deprecated)]
+pub mod metabonding_setup;
+
+use metabonding_setup::*;
+use multiversx_sc_scenario::rust_biguint;
+
+#[test]
+fn vesting_rewards_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+
+    // first project ends in week 3, so rewards vest in weeks 5 and 6
+    mb_setup
+        .call_set_project_vesting(FIRST_PROJ_ID, 1, 2)
+        .assert_ok();
+
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    // cannot change vesting after start
+    mb_setup
+        .call_set_project_vesting(FIRST_PROJ_ID, 0, 1)
+        .assert_user_error("Project already started");
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 2, 25_000, 0, &sig_first_user_week_2)
+        .assert_ok();
+
+    // first project rewards are locked, second project rewards are paid out
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        SECOND_PROJ_TOKEN,
+        &rust_biguint!(50_000_000),
+    );
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, FIRST_PROJ_ID),
+        (0, 41_666_666)
+    );
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, SECOND_PROJ_ID),
+        (0, 0)
+    );
+
+    // week 4 - still in cliff
+    mb_setup.set_current_epoch(33);
+    mb_setup
+        .call_release_vested_rewards(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, FIRST_PROJ_TOKEN, &rust_biguint!(0));
+
+    // week 5 - half is vested
+    mb_setup.advance_one_week();
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, FIRST_PROJ_ID),
+        (20_833_333, 20_833_333)
+    );
+    mb_setup
+        .call_release_vested_rewards(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(20_833_333),
+    );
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, FIRST_PROJ_ID),
+        (0, 20_833_333)
+    );
+
+    // week 6 - everything is vested
+    mb_setup.advance_one_week();
+    mb_setup
+        .call_release_vested_rewards(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(41_666_666),
+    );
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, FIRST_PROJ_ID),
+        (0, 0)
+    );
+}
+
+#[test]
+fn vesting_project_id_reuse_test() {
+    let mut mb_setup = MetabondingSetup::new(metabonding::contract_obj);
+    mb_setup.add_default_projects();
+    mb_setup.deposit_rewards_default_projects();
+    mb_setup
+        .call_set_project_vesting(FIRST_PROJ_ID, 1, 2)
+        .assert_ok();
+    mb_setup.add_default_checkpoints();
+    mb_setup.call_unpause().assert_ok();
+
+    let first_user_addr = mb_setup.first_user_addr.clone();
+    let sig_first_user_week_2 = hex_literal::hex!("b4aadf08eea4cc7c636922511943edbab2ff6ef2558528e0e7b03c7448367989fe860ac091be4d942304f04c86b1eaa0501f36e02819a3c628b4c53f3d3ac801");
+    mb_setup
+        .call_claim_rewards(&first_user_addr, 2, 25_000, 0, &sig_first_user_week_2)
+        .assert_ok();
+
+    // remove the project while the user still has rewards vesting
+    mb_setup.call_remove_project(FIRST_PROJ_ID).assert_ok();
+    mb_setup.advance_one_week();
+    mb_setup.call_execute_timelock_action(1).assert_ok();
+
+    // the ID can't be reused until the vesting positions are released
+    let first_project_owner = mb_setup.first_project_owner.clone();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            SECOND_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            8,
+            2,
+            0,
+        )
+        .assert_user_error("ID already in use");
+
+    // week 5 - half is released
+    mb_setup.set_current_epoch(40);
+    mb_setup
+        .call_release_vested_rewards(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            SECOND_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            8,
+            2,
+            0,
+        )
+        .assert_user_error("ID already in use");
+
+    // week 6 - everything is released
+    mb_setup.advance_one_week();
+    mb_setup
+        .call_release_vested_rewards(&first_user_addr, &[FIRST_PROJ_ID])
+        .assert_ok();
+    mb_setup.b_mock.check_esdt_balance(
+        &first_user_addr,
+        FIRST_PROJ_TOKEN,
+        &rust_biguint!(41_666_666),
+    );
+
+    // the new project starts without any vesting positions
+    mb_setup
+        .call_add_project(
+            FIRST_PROJ_ID,
+            &first_project_owner,
+            SECOND_PROJ_TOKEN,
+            TOTAL_FIRST_PROJ_TOKENS,
+            8,
+            2,
+            0,
+        )
+        .assert_ok();
+    assert_eq!(
+        mb_setup.get_vesting_position(&first_user_addr, FIRST_PROJ_ID),
+        (0, 0)
+    );
+}
@@ -5,9 +5,10 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           20
+// Upgrade:                              1
+// Endpoints:                           93
 // Async Callback (empty):               1
-// Total number of exported functions:  22
+// Total number of exported functions:  96
 
 #![no_std]
 
@@ -18,25 +19,99 @@ multiversx_sc_wasm_adapter::endpoints! {
     metabonding
     (
         init => init
-        changeSigner => change_signer
+        upgrade => upgrade
         pause => pause_endpoint
         unpause => unpause_endpoint
         isPaused => paused_status
         addProject => add_project
-        removeProject => remove_project
-        clearExpiredProjects => clear_expired_projects
+        setProjectMetadata => set_project_metadata
+        setProjectExpirationWeeks => set_project_expiration_weeks
+        setGrowthProgramTarget => set_growth_program_target
+        removeGrowthProgramTarget => remove_growth_program_target
+        depositToGrowthProgram => deposit_to_growth_program
+        refundGrowthProgramDeposit => refund_growth_program_deposit
         getAllProjectIds => get_all_project_ids_view
+        getProjectsAwaitingFunding => get_projects_awaiting_funding
+        getProjectRewardNonce => get_project_reward_nonce
+        getProjectDepositDeadline => get_project_deposit_deadline
+        getProjectExpirationWeeks => get_project_expiration_weeks_view
+        getGrowthProgramTarget => get_growth_program_target
+        getPendingGrowthProgramDeposit => get_pending_growth_program_deposit
         getProjectById => get_project_by_id
         getCurrentWeek => get_current_week
+        getClearCursorWeek => clear_cursor_week
+        clearExpiredProjects => clear_expired_projects
+        editProject => edit_project
+        extendProject => extend_project
+        getProjectExtensionCost => get_project_extension_cost
+        optOutOfProjects => opt_out_of_projects
+        optInToProjects => opt_in_to_projects
+        getOptedOutProjects => get_opted_out_projects
+        setMaxProtocolFeePercentage => set_max_protocol_fee_percentage
+        setProjectProtocolFee => set_project_protocol_fee
+        setTreasuryAddress => set_treasury_address
+        withdrawProtocolFees => withdraw_protocol_fees
+        getProjectProtocolFee => get_project_protocol_fee
+        getTreasuryBalances => get_treasury_balances
+        getMaxProtocolFeePercentage => max_protocol_fee_percentage
+        getTreasuryAddress => treasury_address
+        fundBonusPool => fund_bonus_pool
+        withdrawBonusPool => withdraw_bonus_pool
+        getBonusPoolsForWeek => get_bonus_pools_for_week
+        setProxySettings => set_proxy_settings
+        setProxyWeeklyCap => set_proxy_weekly_cap
+        getProxySettings => get_proxy_settings
+        getProxyWeeklyCap => get_proxy_weekly_cap
+        getProxyClaimStats => get_proxy_claim_stats
         addRewardsCheckpoint => add_rewards_checkpoint
+        addRewardsCheckpoints => add_rewards_checkpoints
         depositRewards => deposit_rewards
         getRewardsForWeek => get_rewards_for_week_pretty
+        setProjectRollover => set_project_rollover
+        isProjectRolloverEnabled => is_project_rollover_enabled
+        getRolledOverAmount => get_rolled_over_amount
         claimRewards => claim_rewards
+        claimRewardsTo => claim_rewards_to
+        claimRewardsWithEnergy => claim_rewards_with_energy
         claimPartialRewards => claim_partial_rewards
+        claimPartialRewardsTo => claim_partial_rewards_to
         getUserClaimableWeeks => get_user_claimable_weeks
+        addClaimDelegate => add_claim_delegate
+        removeClaimDelegate => remove_claim_delegate
+        getClaimDelegates => get_claim_delegates
+        getClaimHistory => get_claim_history
+        getClaimHistoryLength => get_claim_history_length
+        setProjectVesting => set_project_vesting
+        releaseVestedRewards => release_vested_rewards
+        getVestingPosition => get_vesting_position
+        getProjectVesting => get_project_vesting
+        grantRole => grant_role
+        revokeRole => revoke_role
+        hasRole => has_role
+        getRoleMembers => get_role_members
+        getSecp256k1Signer => secp256k1_signer
+        getTimekeepingMode => timekeeping_mode
+        getFirstWeekStartTimestamp => first_week_start_timestamp
+        getWeekLengthSeconds => week_length_seconds
         clearOldStorageFlags => clear_old_storage_flags
-        addSCAddressToWhitelist => add_sc_address_to_whitelist
-        removeSCAddressFromWhitelist => remove_sc_address_from_whitelist
+        setProjectLkmexWeighting => set_project_lkmex_weighting
+        getProjectLkmexWeighting => get_project_lkmex_weighting
+        getTotalEnergyForWeek => get_total_energy_for_week
+        setTimelockDelay => set_timelock_delay
+        removeProject => remove_project
+        changeSigner => change_signer
+        changeSecp256k1Signer => change_secp256k1_signer
+        addProxyToWhitelist => add_proxy_to_whitelist
+        removeProxyFromWhitelist => remove_proxy_from_whitelist
+        executeTimelockAction => execute_timelock_action
+        cancelTimelockAction => cancel_timelock_action
+        getQueuedActions => get_queued_actions
+        getQueuedAction => get_queued_action
+        getTimelockDelay => timelock_delay_epochs
+        getStorageVersion => storage_version
+        getSolvencyReport => get_solvency_report
+        getTokenSurplus => get_token_surplus
+        withdrawSurplus => withdraw_surplus
         isSCAddressWhitelisted => is_sc_address_whitelisted
     )
 }